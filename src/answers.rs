@@ -0,0 +1,351 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{error::AocError, input::Source};
+
+// Known answers live next to the inputs they are for.
+pub const FILE: &str = "answers.toml";
+
+pub fn path(dir: &Path) -> PathBuf {
+    dir.join(FILE)
+}
+
+// Known answers for parts 1 and 2, keyed by day and input file name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    // The directory the input names are relative to.
+    pub dir: PathBuf,
+    known: BTreeMap<(u8, String), [Option<String>; 2]>,
+}
+
+// Anything but a comment after a value or a table header is an error.
+fn trailing(line: &str, rest: &str) -> Result<(), AocError> {
+    let rest = rest.trim();
+    if rest.is_empty() || rest.starts_with('#') {
+        Ok(())
+    } else {
+        Err(AocError::at(line, rest, format!("unexpected '{rest}'")))
+    }
+}
+
+fn unescape(line: &str, s: &str) -> Result<String, AocError> {
+    let mut unescaped = String::new();
+    let mut chars = s.char_indices();
+    while let Some((i, c)) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        unescaped.push(match chars.next() {
+            Some((_, 'n')) => '\n',
+            Some((_, 'r')) => '\r',
+            Some((_, 't')) => '\t',
+            Some((_, '"')) => '"',
+            Some((_, '\\')) => '\\',
+            _ => return Err(AocError::at(line, &s[i..], "invalid escape sequence")),
+        });
+    }
+    Ok(unescaped)
+}
+
+fn closing_quote(s: &str) -> Option<usize> {
+    let mut escaped = false;
+    s.char_indices()
+        .find(|&(_, c)| {
+            let closing = c == '"' && !escaped;
+            escaped = c == '\\' && !escaped;
+            closing
+        })
+        .map(|(i, _)| i)
+}
+
+// `dayN."input name"`, the name may be bare if it is a valid TOML key.
+fn table_key(line: &str, header: &str) -> Result<(u8, String), AocError> {
+    let (day, name) = header
+        .split_once('.')
+        .ok_or_else(|| AocError::at(line, header, "expected [dayN.\"input name\"]"))?;
+    let day = day
+        .trim()
+        .strip_prefix("day")
+        .and_then(|d| d.parse::<u8>().ok())
+        .filter(|d| (1..=25).contains(d))
+        .ok_or_else(|| {
+            AocError::at(
+                line,
+                day,
+                format!("expected day1 to day25, found '{}'", day.trim()),
+            )
+        })?;
+    let name = name.trim();
+    let unquoted = name
+        .strip_prefix('"')
+        .and_then(|n| n.strip_suffix('"'))
+        .unwrap_or(name);
+    if unquoted.is_empty() || unquoted.contains(['"', '/', '\\']) {
+        return Err(AocError::at(
+            line,
+            name,
+            format!("expected an input file name, found '{name}'"),
+        ));
+    }
+    Ok((day, unquoted.to_string()))
+}
+
+impl Answers {
+    // A subset of TOML, a `[dayN."input name"]` table per input with `part1` and `part2`
+    // strings or integers. Multi-line strings are used for answers drawn in ASCII art.
+    pub fn parse(dir: &Path, s: &str) -> Result<Self, AocError> {
+        let mut known = BTreeMap::new();
+        let mut table = None;
+        let mut lines = s.lines().enumerate();
+        while let Some((n, line)) = lines.next() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            if let Some(header) = trimmed.strip_prefix('[') {
+                let (header, rest) = header
+                    .split_once(']')
+                    .ok_or_else(|| AocError::at(line, trimmed, "expected ']'").on_line(n + 1))?;
+                trailing(line, rest).map_err(|e| e.on_line(n + 1))?;
+                let key = table_key(line, header).map_err(|e| e.on_line(n + 1))?;
+                if known.insert(key.clone(), [None, None]).is_some() {
+                    return Err(AocError::at(
+                        line,
+                        header,
+                        format!("duplicate answers for day{} {}", key.0, key.1),
+                    )
+                    .on_line(n + 1));
+                }
+                table = Some(key);
+                continue;
+            }
+
+            let error =
+                |part: &str, message: String| AocError::at(line, part, message).on_line(n + 1);
+            let (key, value) = trimmed
+                .split_once('=')
+                .ok_or_else(|| error(trimmed, "expected 'part1 = answer'".to_string()))?;
+            let part = match key.trim() {
+                "part1" => 0,
+                "part2" => 1,
+                k => {
+                    return Err(error(
+                        key,
+                        format!("unknown key '{k}', expected part1 or part2"),
+                    ))
+                }
+            };
+            let Some(answers) = table.as_ref().and_then(|t| known.get_mut(t)) else {
+                return Err(error(
+                    trimmed,
+                    "expected a [dayN.\"input name\"] table first".to_string(),
+                ));
+            };
+            if answers[part].is_some() {
+                return Err(error(key, format!("duplicate key '{}'", key.trim())));
+            }
+
+            let value = value.trim_start();
+            let answer = if let Some(rest) = value.strip_prefix("\"\"\"") {
+                // The line break right after the opening quotes is not part of the string.
+                match rest.split_once("\"\"\"") {
+                    Some((s, after)) => {
+                        trailing(line, after).map_err(|e| e.on_line(n + 1))?;
+                        unescape(line, s).map_err(|e| e.on_line(n + 1))?
+                    }
+                    None => {
+                        let mut parts = Vec::new();
+                        if !rest.is_empty() {
+                            parts.push(unescape(line, rest).map_err(|e| e.on_line(n + 1))?);
+                        }
+                        loop {
+                            let Some((m, l)) = lines.next() else {
+                                return Err(error(value, "unterminated string".to_string()));
+                            };
+                            let (s, after) = l.split_once("\"\"\"").unwrap_or((l, ""));
+                            parts.push(unescape(l, s).map_err(|e| e.on_line(m + 1))?);
+                            if s.len() != l.len() {
+                                trailing(l, after).map_err(|e| e.on_line(m + 1))?;
+                                break;
+                            }
+                        }
+                        parts.join("\n")
+                    }
+                }
+            } else if let Some(rest) = value.strip_prefix('"') {
+                let end = closing_quote(rest)
+                    .ok_or_else(|| error(value, "unterminated string".to_string()))?;
+                trailing(line, &rest[end + 1..]).map_err(|e| e.on_line(n + 1))?;
+                unescape(line, &rest[..end]).map_err(|e| e.on_line(n + 1))?
+            } else {
+                let (number, _) = value.split_once('#').unwrap_or((value, ""));
+                let number = number.trim();
+                number.parse::<i64>().map_err(|_| {
+                    error(
+                        value,
+                        format!("expected a string or an integer, found '{number}'"),
+                    )
+                })?;
+                number.to_string()
+            };
+            answers[part] = Some(answer);
+        }
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            known,
+        })
+    }
+
+    pub fn get(&self, day: u8, name: &str) -> [Option<String>; 2] {
+        self.known
+            .get(&(day, name.to_string()))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    // The known answers for `source`, if it is one of the listed inputs.
+    pub fn expected(&self, day: u8, source: &Source) -> [Option<String>; 2] {
+        match source {
+            Source::File(path) if path.parent().is_some_and(|p| same_dir(p, &self.dir)) => path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .map_or_else(Default::default, |n| self.get(day, n)),
+            _ => Default::default(),
+        }
+    }
+
+    // Names of the inputs listed for `day`.
+    pub fn inputs(&self, day: u8) -> Vec<&str> {
+        self.known
+            .keys()
+            .filter(|(d, _)| *d == day)
+            .map(|(_, name)| name.as_str())
+            .collect()
+    }
+}
+
+// Whether `a` and `b` are the same directory however they are spelled, `./inputs` and
+// `inputs/` for example.
+fn same_dir(a: &Path, b: &Path) -> bool {
+    // The parent of a bare file name is empty.
+    let dir = |p: &Path| match p.as_os_str().is_empty() {
+        true => PathBuf::from("."),
+        false => p.to_path_buf(),
+    };
+    let (a, b) = (dir(a), dir(b));
+    a == b || matches!((fs::canonicalize(&a), fs::canonicalize(&b)), (Ok(a), Ok(b)) if a == b)
+}
+
+// The answers file of the input directory `dir`.
+pub fn load(dir: &Path) -> Result<Answers, String> {
+    let path = path(dir);
+    let s = fs::read_to_string(&path)
+        .map_err(|e| format!("cannot read answers {}: {e}", path.display()))?;
+    Answers::parse(dir, &s)
+        .map_err(|e| format!("malformed answers {}, {}", path.display(), e.diagnostic(&s)))
+}
+
+#[test]
+fn test_parse() {
+    let dir = Path::new("inputs");
+    let answers = Answers::parse(
+        dir,
+        "# known answers
+
+[day1.\"aoc_1.input\"]
+part1 = 1559 # integers are fine
+part2 = \"1600\"
+
+[day13.aoc_13_sample]
+part1 = \"a \\\"b\\\"\\tc\"
+part2 = \"\"\"
+#
+#\"\"\"
+",
+    )
+    .unwrap();
+    assert_eq!(
+        answers.get(1, "aoc_1.input"),
+        [Some("1559".into()), Some("1600".into())]
+    );
+    assert_eq!(
+        answers.get(13, "aoc_13_sample"),
+        [Some("a \"b\"\tc".into()), Some("#\n#".into())]
+    );
+    assert_eq!(answers.get(2, "aoc_2.input"), [None, None]);
+    assert_eq!(answers.inputs(1), ["aoc_1.input"]);
+    assert_eq!(
+        answers.expected(1, &Source::File(dir.join("aoc_1.input")))[0].as_deref(),
+        Some("1559")
+    );
+    assert_eq!(
+        answers.expected(1, &Source::from("elsewhere/aoc_1.input")),
+        [None, None]
+    );
+    assert_eq!(answers.expected(1, &Source::Stdin), [None, None]);
+    // Other spellings of an existing input directory.
+    let answers = Answers::parse(Path::new("src/inputs"), "[day1.a]\npart1 = 1").unwrap();
+    for path in ["./src/inputs/a", "src/inputs/../inputs/a", "src//inputs/a"] {
+        assert_eq!(
+            answers.expected(1, &Source::from(path))[0].as_deref(),
+            Some("1"),
+            "{path}"
+        );
+    }
+    assert_eq!(answers.expected(1, &Source::from("src/a")), [None, None]);
+
+    let error = |s| Answers::parse(dir, s).unwrap_err().to_string();
+    assert_eq!(
+        error("part1 = 1"),
+        "line 1, column 1: expected a [dayN.\"input name\"] table first"
+    );
+    assert_eq!(
+        error("[day26.a]"),
+        "line 1, column 2: expected day1 to day25, found 'day26'"
+    );
+    assert_eq!(
+        error("[day1.a]\npart3 = 1"),
+        "line 2, column 1: unknown key 'part3', expected part1 or part2"
+    );
+    assert_eq!(
+        error("[day1.a]\npart1 = 1\npart1 = 2"),
+        "line 3, column 1: duplicate key 'part1'"
+    );
+    assert_eq!(
+        error("[day1.a]\n[day1.\"a\"]"),
+        "line 2, column 2: duplicate answers for day1 a"
+    );
+    assert_eq!(
+        error("[day1.a]\npart1 = \"1\" 2"),
+        "line 2, column 13: unexpected '2'"
+    );
+    assert_eq!(
+        error("[day1.a]\npart1 = \"\"\"\n1\n"),
+        "line 2, column 9: unterminated string"
+    );
+    assert_eq!(
+        error("[day1.a]\npart1 = one"),
+        "line 2, column 9: expected a string or an integer, found 'one'"
+    );
+}
+
+#[test]
+fn test_answers_file() {
+    // Every puzzle input listed has the answers for both parts. Days fresh from `new` are not
+    // listed until they are solved.
+    let answers = load(Path::new(crate::input::INPUT_DIR)).unwrap();
+    for day in &crate::solver::DAYS {
+        let name = crate::input::file_name(day.day);
+        if !answers.inputs(day.day).contains(&name.as_str()) {
+            continue;
+        }
+        assert!(
+            answers.get(day.day, &name).iter().all(Option::is_some),
+            "{name}"
+        );
+    }
+}
@@ -0,0 +1,264 @@
+use std::{collections::BTreeMap, fs, iter, path::Path, time::Duration};
+
+use crate::{
+    error::AocError,
+    solver::{Day, Solution},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    // Percentiles use the nearest rank, `samples` must not be empty.
+    pub fn new(samples: &[Duration]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let rank =
+            |p: f64| sorted[((p * sorted.len() as f64).ceil() as usize).clamp(1, sorted.len()) - 1];
+        let mean = sorted.iter().map(|d| d.as_secs_f64()).sum::<f64>() / sorted.len() as f64;
+        let variance = sorted
+            .iter()
+            .map(|d| (d.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / sorted.len() as f64;
+        Self {
+            min: sorted[0],
+            median: rank(0.5),
+            p95: rank(0.95),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+// Timings of one phase of a day, the phase is `parse`, `part1` or `part2`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Measurement {
+    pub day: u8,
+    pub phase: String,
+    pub stats: Stats,
+}
+
+// Solves `input` `warmup` times without measuring, then `iterations` times
+// timing the parse and each part separately.
+pub fn measure(
+    day: &Day,
+    input: &str,
+    part: Option<u8>,
+    warmup: u32,
+    iterations: u32,
+) -> Result<Vec<Measurement>, AocError> {
+    for _ in 0..warmup {
+        (day.solve)(input, part, &mut |_| {})?;
+    }
+    let runs = (0..iterations.max(1))
+        .map(|_| (day.solve)(input, part, &mut |_| {}))
+        .collect::<Result<Vec<Solution>, AocError>>()?;
+    let measurement = |phase: String, samples: Vec<Duration>| Measurement {
+        day: day.day,
+        phase,
+        stats: Stats::new(&samples),
+    };
+    Ok(iter::once(measurement(
+        "parse".to_string(),
+        runs.iter().map(|s| s.parse).collect(),
+    ))
+    .chain(runs[0].parts.iter().enumerate().map(|(i, p)| {
+        measurement(
+            format!("part{}", p.part),
+            runs.iter().map(|s| s.parts[i].elapsed).collect(),
+        )
+    }))
+    .collect())
+}
+
+// Saved measurements keyed by (day, phase).
+pub type Baseline = BTreeMap<(u8, String), Stats>;
+
+// One line per measurement: `day phase min median p95 stddev`, in nanoseconds.
+pub fn parse_baseline(s: &str) -> Result<Baseline, String> {
+    s.lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty() && !l.starts_with('#'))
+        .map(|(n, l)| {
+            let err = || format!("line {}: invalid baseline entry '{l}'", n + 1);
+            let fields = l.split_whitespace().collect::<Vec<&str>>();
+            let [day, phase, min, median, p95, stddev] = fields[..] else {
+                return Err(err());
+            };
+            let ns = |s: &str| {
+                s.parse::<u64>()
+                    .map(Duration::from_nanos)
+                    .map_err(|_| err())
+            };
+            Ok((
+                (day.parse::<u8>().map_err(|_| err())?, phase.to_string()),
+                Stats {
+                    min: ns(min)?,
+                    median: ns(median)?,
+                    p95: ns(p95)?,
+                    stddev: ns(stddev)?,
+                },
+            ))
+        })
+        .collect()
+}
+
+pub fn format_baseline(baseline: &Baseline) -> String {
+    iter::once("# day phase min_ns median_ns p95_ns stddev_ns".to_string())
+        .chain(baseline.iter().map(|((day, phase), s)| {
+            format!(
+                "{day} {phase} {} {} {} {}",
+                s.min.as_nanos(),
+                s.median.as_nanos(),
+                s.p95.as_nanos(),
+                s.stddev.as_nanos()
+            )
+        }))
+        .map(|l| l + "\n")
+        .collect()
+}
+
+pub fn load_baseline(path: &Path) -> Result<Baseline, String> {
+    fs::read_to_string(path)
+        .map_err(|e| format!("cannot read baseline {}: {e}", path.display()))
+        .and_then(|s| parse_baseline(&s).map_err(|e| format!("{}: {e}", path.display())))
+}
+
+// Adds `measurements` to the baseline at `path`, keeping entries of days that were not benchmarked.
+pub fn save_baseline(path: &Path, measurements: &[Measurement]) -> Result<(), String> {
+    let mut baseline = if path.exists() {
+        load_baseline(path)?
+    } else {
+        Baseline::new()
+    };
+    measurements.iter().for_each(|m| {
+        baseline.insert((m.day, m.phase.clone()), m.stats);
+    });
+    fs::write(path, format_baseline(&baseline))
+        .map_err(|e| format!("cannot write baseline {}: {e}", path.display()))
+}
+
+// Relative change of the median against the baseline, e.g. 0.25 for 25% slower.
+pub fn change(m: &Measurement, baseline: &Baseline) -> Option<f64> {
+    baseline
+        .get(&(m.day, m.phase.clone()))
+        .map(|b| m.stats.median.as_secs_f64() / b.median.as_secs_f64().max(f64::EPSILON) - 1.0)
+}
+
+fn duration(d: Duration) -> String {
+    format!("{d:.1?}")
+}
+
+// One row per measurement, regressions are medians more than `threshold` slower than the baseline.
+pub fn table(measurements: &[Measurement], baseline: &Baseline, threshold: f64) -> String {
+    iter::once(format!(
+        "{:>5}  {:<6} {:>10} {:>10} {:>10} {:>10}  baseline",
+        "day", "phase", "min", "median", "p95", "stddev"
+    ))
+    .chain(measurements.iter().map(|m| {
+        let change = change(m, baseline).map_or("-".to_string(), |c| {
+            let regression = if c > threshold { " REGRESSION" } else { "" };
+            format!("{:+.1}%{regression}", c * 100.0)
+        });
+        format!(
+            "{:>5}  {:<6} {:>10} {:>10} {:>10} {:>10}  {change}",
+            m.day,
+            m.phase,
+            duration(m.stats.min),
+            duration(m.stats.median),
+            duration(m.stats.p95),
+            duration(m.stats.stddev)
+        )
+    }))
+    .collect::<Vec<String>>()
+    .join("\n")
+}
+
+pub fn regressions(measurements: &[Measurement], baseline: &Baseline, threshold: f64) -> usize {
+    measurements
+        .iter()
+        .filter(|m| change(m, baseline).is_some_and(|c| c > threshold))
+        .count()
+}
+
+#[test]
+fn test_stats() {
+    let samples = (1..=20)
+        .rev()
+        .map(Duration::from_millis)
+        .collect::<Vec<_>>();
+    let stats = Stats::new(&samples);
+    assert_eq!(stats.min, Duration::from_millis(1));
+    assert_eq!(stats.median, Duration::from_millis(10));
+    assert_eq!(stats.p95, Duration::from_millis(19));
+    assert_eq!(stats.stddev.as_micros(), 5766);
+    let stats = Stats::new(&[Duration::from_millis(3)]);
+    assert_eq!(
+        (stats.median, stats.p95),
+        (Duration::from_millis(3), Duration::from_millis(3))
+    );
+    assert_eq!(stats.stddev, Duration::ZERO);
+}
+
+#[test]
+fn test_measure() {
+    let day = crate::solver::find(crate::solver::YEAR, 17).unwrap();
+    let ms = measure(day, "target area: x=20..30, y=-10..-5", Some(2), 1, 5).unwrap();
+    assert_eq!(
+        ms.iter().map(|m| m.phase.as_str()).collect::<Vec<_>>(),
+        ["parse", "part2"]
+    );
+    assert!(ms.iter().all(|m| m.day == 17 && m.stats.min <= m.stats.p95));
+    assert!(measure(day, "target area: x=20..30", None, 1, 5).is_err());
+}
+
+#[test]
+fn test_baseline() {
+    let stats = |ms| Stats {
+        min: Duration::from_millis(ms),
+        median: Duration::from_millis(ms),
+        p95: Duration::from_millis(ms),
+        stddev: Duration::ZERO,
+    };
+    let ms = [
+        Measurement {
+            day: 5,
+            phase: "parse".to_string(),
+            stats: stats(1),
+        },
+        Measurement {
+            day: 5,
+            phase: "part1".to_string(),
+            stats: stats(10),
+        },
+    ];
+    let baseline = parse_baseline(
+        "# day phase min_ns median_ns p95_ns stddev_ns\n5 parse 1000000 1000000 1000000 0\n5 part1 5000000 8000000 9000000 10\n",
+    )
+    .unwrap();
+    assert_eq!(
+        baseline[&(5, "part1".to_string())].median,
+        Duration::from_millis(8)
+    );
+    assert_eq!(
+        parse_baseline(&format_baseline(&baseline)),
+        Ok(baseline.clone())
+    );
+    assert_eq!(change(&ms[0], &baseline), Some(0.0));
+    assert_eq!(change(&ms[1], &baseline), Some(0.25));
+    assert_eq!(regressions(&ms, &baseline, 0.1), 1);
+    assert_eq!(regressions(&ms, &baseline, 0.3), 0);
+    assert_eq!(
+        table(&ms, &baseline, 0.1),
+        "  day  phase         min     median        p95     stddev  baseline
+    5  parse       1.0ms      1.0ms      1.0ms      0.0ns  +0.0%
+    5  part1      10.0ms     10.0ms     10.0ms      0.0ns  +25.0% REGRESSION"
+    );
+    assert!(parse_baseline("5 parse 1 2 3").is_err());
+    assert!(parse_baseline("x parse 1 2 3 4").is_err());
+}
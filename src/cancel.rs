@@ -0,0 +1,72 @@
+use std::{
+    cell::Cell,
+    panic,
+    time::{Duration, Instant},
+};
+
+// Cooperative cancellation: the runner gives each day a time budget and
+// solvers poll `check` in their hot loops to give up once it is spent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Token {
+    deadline: Option<Instant>,
+}
+
+// Unwind payload of a cancelled solver, see `check`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+thread_local! {
+    static CURRENT: Cell<Token> = const { Cell::new(Token { deadline: None }) };
+}
+
+impl Token {
+    pub fn new(budget: Option<Duration>) -> Self {
+        Self {
+            deadline: budget.map(|b| Instant::now() + b),
+        }
+    }
+
+    // The time left until the deadline, None without one.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.deadline
+            .map(|d| d.saturating_duration_since(Instant::now()))
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.deadline.is_some_and(|d| Instant::now() >= d)
+    }
+}
+
+// Runs `f` with `token` as the current token of this thread.
+pub fn with_token<R>(token: Token, f: impl FnOnce() -> R) -> R {
+    let previous = CURRENT.replace(token);
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(f));
+    CURRENT.set(previous);
+    result.unwrap_or_else(|payload| panic::resume_unwind(payload))
+}
+
+pub fn current() -> Token {
+    CURRENT.get()
+}
+
+// Unwinds with `Cancelled` if the current token is cancelled, the runner
+// catches it and reports the day as timed out.
+pub fn check() {
+    if current().is_cancelled() {
+        panic::resume_unwind(Box::new(Cancelled));
+    }
+}
+
+#[test]
+fn test_cancel() {
+    assert!(!current().is_cancelled());
+    check();
+    assert!(!Token::new(Some(Duration::from_secs(60))).is_cancelled());
+    let token = Token::new(Some(Duration::ZERO));
+    assert!(token.is_cancelled());
+    assert_eq!(token.remaining(), Some(Duration::ZERO));
+    assert_eq!(Token::default().remaining(), None);
+    let payload = panic::catch_unwind(|| with_token(token, check)).unwrap_err();
+    assert!(payload.downcast_ref::<Cancelled>().is_some());
+    assert_eq!(current(), Token::default());
+}
@@ -0,0 +1,294 @@
+use std::{path::PathBuf, time::Duration};
+
+use crate::{input::Source, solver};
+
+pub const USAGE: &str = "usage: aoc2021 [COMMAND] [OPTIONS]
+
+commands:
+    run [DAYS]      run the selected days (default: all), DAYS is e.g. 7, 1,5,9 or 10-18
+    check [DAYS]    run the selected days on every input listed in answers.toml and
+                    compare against the known answers
+    bench [DAYS]    time parse, part 1 and part 2 of the selected days separately
+    list            list all days and their status
+    new DAY         create src/dayDAY.rs from src/template.rs with empty input files
+                    and register it
+    gen DAY         print a random input for DAY, e.g. to scale test a solver
+    help            print this message
+
+options:
+    --part 1|2      only report the given part
+    --input PATH    read the puzzle input from PATH, or stdin if PATH is -
+                    (run and bench, single day only)
+    --format FMT    output format: text, json, csv or junit
+    --timeout SECS  give up on a day after SECS seconds (run and check), a day that does
+                    not stop by itself keeps a core busy in the background until it ends
+    --jobs N        solve up to N days in parallel (run and check, default: one per core)
+    --sequential    solve one day at a time, same as --jobs 1
+    --mem           count allocations, bytes allocated and peak heap per day (run)
+
+bench options:
+    --warmup N          untimed runs before measuring (default: 3)
+    --iterations N      timed runs per day (default: 10)
+    --baseline PATH     compare the medians against the baseline in PATH
+    --save-baseline PATH
+                        add the measurements to the baseline in PATH
+    --threshold PCT     report medians more than PCT% slower than the baseline
+                        as regressions (default: 10)
+
+gen options:
+    --seed N        seed of the generated input, the same seed gives the same input
+                    (default: 0)
+    --size N        size of the generated input, e.g. the side of a grid or the number
+                    of lines (default: about that of a real input)
+
+environment:
+    AOC_INPUT_DIR   directory with aoc_DAY.input files and answers.toml (default: src/inputs)";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Run(Vec<u8>),
+    Check(Vec<u8>),
+    Bench(Vec<u8>),
+    List,
+    New(u8),
+    Gen(u8),
+    Help,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+    Junit,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchOptions {
+    pub warmup: u32,
+    pub iterations: u32,
+    pub baseline: Option<PathBuf>,
+    pub save_baseline: Option<PathBuf>,
+    // Relative slowdown of a median that counts as a regression, e.g. 0.1.
+    pub threshold: f64,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            warmup: 3,
+            iterations: 10,
+            baseline: None,
+            save_baseline: None,
+            threshold: 0.1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GenOptions {
+    pub seed: u64,
+    // None means the size of a real input, see `gen::generate`.
+    pub size: Option<usize>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Args {
+    pub command: Command,
+    pub part: Option<u8>,
+    pub input: Option<Source>,
+    pub format: Format,
+    pub timeout: Option<Duration>,
+    // Size of the runner's thread pool, None means one thread per core.
+    pub jobs: Option<usize>,
+    // Whether to count allocations, see `mem::Counting`.
+    pub mem: bool,
+    pub bench: BenchOptions,
+    pub gen: GenOptions,
+}
+
+fn all_days() -> Vec<u8> {
+    solver::DAYS.iter().map(|d| d.day).collect()
+}
+
+pub fn parse(args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut part = None;
+    let mut input = None;
+    let mut format = Format::Text;
+    let mut timeout = None;
+    let mut jobs = None;
+    let mut mem = false;
+    let mut bench = BenchOptions::default();
+    let mut gen = GenOptions::default();
+    let mut positional = Vec::new();
+    let mut args = args;
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("{name} requires a value"));
+        match arg.as_str() {
+            "--part" => match value("--part")?.as_str() {
+                "1" => part = Some(1),
+                "2" => part = Some(2),
+                p => return Err(format!("invalid part '{p}', expected 1 or 2")),
+            },
+            "--input" => input = Some(Source::from(value("--input")?.as_str())),
+            "--format" => match value("--format")?.as_str() {
+                "text" => format = Format::Text,
+                "json" => format = Format::Json,
+                "csv" => format = Format::Csv,
+                "junit" => format = Format::Junit,
+                f => return Err(format!("unsupported format '{f}'")),
+            },
+            "--timeout" => match value("--timeout")?.parse::<f64>() {
+                Ok(t) if t > 0.0 && t.is_finite() => timeout = Some(Duration::from_secs_f64(t)),
+                _ => return Err("--timeout requires a positive number of seconds".to_string()),
+            },
+            "--jobs" => match value("--jobs")?.parse::<usize>() {
+                Ok(n) if n > 0 => jobs = Some(n),
+                _ => return Err("--jobs requires a positive number of threads".to_string()),
+            },
+            "--sequential" => jobs = Some(1),
+            "--mem" => mem = true,
+            "--warmup" => match value("--warmup")?.parse::<u32>() {
+                Ok(n) => bench.warmup = n,
+                _ => return Err("--warmup requires a number of runs".to_string()),
+            },
+            "--iterations" => match value("--iterations")?.parse::<u32>() {
+                Ok(n) if n > 0 => bench.iterations = n,
+                _ => return Err("--iterations requires a positive number of runs".to_string()),
+            },
+            "--baseline" => bench.baseline = Some(PathBuf::from(value("--baseline")?)),
+            "--save-baseline" => {
+                bench.save_baseline = Some(PathBuf::from(value("--save-baseline")?))
+            }
+            "--threshold" => match value("--threshold")?.parse::<f64>() {
+                Ok(t) if t >= 0.0 && t.is_finite() => bench.threshold = t / 100.0,
+                _ => return Err("--threshold requires a non-negative percentage".to_string()),
+            },
+            "--seed" => match value("--seed")?.parse::<u64>() {
+                Ok(n) => gen.seed = n,
+                _ => return Err("--seed requires a number".to_string()),
+            },
+            "--size" => match value("--size")?.parse::<usize>() {
+                Ok(n) if n > 0 => gen.size = Some(n),
+                _ => return Err("--size requires a positive number".to_string()),
+            },
+            "-h" | "--help" => positional.insert(0, "help".to_string()),
+            a if a.starts_with('-') && a != "-" => return Err(format!("unknown option '{a}'")),
+            _ => positional.push(arg),
+        }
+    }
+
+    let mut positional = positional.into_iter();
+    let days = |spec: Option<String>| spec.map_or(Ok(all_days()), |s| solver::parse_days(&s));
+    let command = match positional.next().as_deref() {
+        None => Command::Run(all_days()),
+        Some("run") => Command::Run(days(positional.next())?),
+        Some("check") => Command::Check(days(positional.next())?),
+        Some("bench") => Command::Bench(days(positional.next())?),
+        Some("list") => Command::List,
+        Some("new") => match positional.next().map(|s| solver::parse_days(&s)) {
+            Some(Ok(d)) if d.len() == 1 => Command::New(d[0]),
+            Some(Err(e)) => return Err(e),
+            _ => return Err("new requires a single day".to_string()),
+        },
+        Some("gen") => match positional.next().map(|s| solver::parse_days(&s)) {
+            Some(Ok(d)) if d.len() == 1 => Command::Gen(d[0]),
+            Some(Err(e)) => return Err(e),
+            _ => return Err("gen requires a single day".to_string()),
+        },
+        Some("help") => Command::Help,
+        // `aoc2021 5` is shorthand for `aoc2021 run 5`.
+        Some(spec) => Command::Run(solver::parse_days(spec)?),
+    };
+    if let Some(extra) = positional.next() {
+        return Err(format!("unexpected argument '{extra}'"));
+    }
+    if input.is_some() && !matches!(&command, Command::Run(d) | Command::Bench(d) if d.len() == 1) {
+        return Err("--input requires a single day".to_string());
+    }
+    Ok(Args {
+        command,
+        part,
+        input,
+        format,
+        timeout,
+        jobs,
+        mem,
+        bench,
+        gen,
+    })
+}
+
+#[test]
+fn test_parse() {
+    let parse_str = |s: &str| parse(s.split_whitespace().map(String::from));
+    assert_eq!(parse_str("").unwrap().command, Command::Run(all_days()));
+    assert_eq!(parse_str("5").unwrap().command, Command::Run(vec![5]));
+    assert_eq!(
+        parse_str("run 1,3 --part 2").unwrap(),
+        Args {
+            command: Command::Run(vec![1, 3]),
+            part: Some(2),
+            input: None,
+            format: Format::Text,
+            timeout: None,
+            jobs: None,
+            mem: false,
+            bench: BenchOptions::default(),
+            gen: GenOptions::default(),
+        }
+    );
+    assert_eq!(
+        parse_str("run 4 --input foo.txt").unwrap().input,
+        Some(Source::from("foo.txt"))
+    );
+    assert_eq!(parse_str("4 --input -").unwrap().input, Some(Source::Stdin));
+    assert_eq!(parse_str("new 19").unwrap().command, Command::New(19));
+    assert_eq!(parse_str("list").unwrap().command, Command::List);
+    assert!(parse_str("run 1-3 --input foo.txt").is_err());
+    assert!(parse_str("check 4 --input foo.txt").is_err());
+    assert!(parse_str("run --part 3").is_err());
+    assert_eq!(
+        parse_str("check --format junit").unwrap().format,
+        Format::Junit
+    );
+    assert!(parse_str("run --format yaml").is_err());
+    assert_eq!(
+        parse_str("run --timeout 1.5").unwrap().timeout,
+        Some(Duration::from_millis(1500))
+    );
+    assert!(parse_str("run --timeout 0").is_err());
+    assert!(parse_str("run --timeout soon").is_err());
+    assert_eq!(parse_str("run --jobs 3").unwrap().jobs, Some(3));
+    assert_eq!(parse_str("check --sequential").unwrap().jobs, Some(1));
+    assert!(parse_str("run --jobs 0").is_err());
+    assert!(parse_str("run 12 --mem").unwrap().mem);
+    assert_eq!(
+        parse_str("bench 5 --warmup 0 --iterations 50 --baseline b.txt --threshold 5")
+            .unwrap()
+            .bench,
+        BenchOptions {
+            warmup: 0,
+            iterations: 50,
+            baseline: Some(PathBuf::from("b.txt")),
+            save_baseline: None,
+            threshold: 0.05,
+        }
+    );
+    assert!(parse_str("bench --iterations 0").is_err());
+    assert_eq!(
+        parse_str("gen 15 --seed 7 --size 500").unwrap(),
+        Args {
+            command: Command::Gen(15),
+            gen: GenOptions {
+                seed: 7,
+                size: Some(500),
+            },
+            ..parse_str("").unwrap()
+        }
+    );
+    assert!(parse_str("gen 1-3").is_err());
+    assert!(parse_str("gen 12 --size 0").is_err());
+    assert!(parse_str("new").is_err());
+    assert!(parse_str("list 5").is_err());
+}
@@ -0,0 +1,100 @@
+// Stable versions of the nightly `iter_map_windows` and `cmp_minmax` features, the `nightly`
+// feature uses the ones from std instead.
+#[cfg(not(feature = "nightly"))]
+use std::collections::VecDeque;
+
+#[cfg(feature = "nightly")]
+pub use std::cmp::minmax;
+#[cfg(feature = "nightly")]
+pub use std::iter::MapWindows;
+
+// `[a, b]` if `a <= b`, `[b, a]` otherwise.
+#[cfg(not(feature = "nightly"))]
+pub fn minmax<T: Ord>(a: T, b: T) -> [T; 2] {
+    if b < a {
+        [b, a]
+    } else {
+        [a, b]
+    }
+}
+
+// Calls a function on each window of `N` consecutive items, see `Iterator::map_windows`.
+#[cfg(not(feature = "nightly"))]
+pub struct MapWindows<I: Iterator, F, const N: usize> {
+    iter: I,
+    f: F,
+    window: VecDeque<I::Item>,
+}
+
+#[cfg(not(feature = "nightly"))]
+impl<I: Iterator, F, const N: usize> MapWindows<I, F, N> {
+    fn new(iter: I, f: F) -> Self {
+        assert!(N != 0, "window size must not be zero");
+        Self {
+            iter,
+            f,
+            window: VecDeque::with_capacity(N),
+        }
+    }
+}
+
+#[cfg(not(feature = "nightly"))]
+impl<I, F, R, const N: usize> Iterator for MapWindows<I, F, N>
+where
+    I: Iterator,
+    F: FnMut(&[I::Item; N]) -> R,
+{
+    type Item = R;
+
+    fn next(&mut self) -> Option<R> {
+        if self.window.len() == N {
+            self.window.pop_front();
+        }
+        while self.window.len() < N {
+            self.window.push_back(self.iter.next()?);
+        }
+        let window = <&[I::Item; N]>::try_from(&*self.window.make_contiguous()).unwrap();
+        Some((self.f)(window))
+    }
+}
+
+// Not named `map_windows`, which would collide with the unstable std method.
+pub trait MapWindowsExt: Iterator + Sized {
+    fn map_array_windows<F, R, const N: usize>(self, f: F) -> MapWindows<Self, F, N>
+    where
+        F: FnMut(&[Self::Item; N]) -> R,
+    {
+        #[cfg(feature = "nightly")]
+        return Iterator::map_windows(self, f);
+        #[cfg(not(feature = "nightly"))]
+        MapWindows::new(self, f)
+    }
+}
+
+impl<I: Iterator> MapWindowsExt for I {}
+
+#[test]
+fn test_compat() {
+    assert_eq!(minmax(3, 1), [1, 3]);
+    assert_eq!(minmax(1, 3), [1, 3]);
+    assert_eq!(
+        (1..=5)
+            .map_array_windows(|[a, b]| a * b)
+            .collect::<Vec<i32>>(),
+        [2, 6, 12, 20]
+    );
+    assert_eq!(
+        "abcd"
+            .chars()
+            .map_array_windows(|w: &[char; 3]| w.iter().collect::<String>())
+            .collect::<Vec<String>>(),
+        ["abc", "bcd"]
+    );
+    assert_eq!((1..=2).map_array_windows(|[a, b, c]| a + b + c).count(), 0);
+    // Items do not need to be `Clone`.
+    let windows = vec![String::from("a"), String::from("b")]
+        .into_iter()
+        .map_array_windows(|[a, b]| format!("{a}{b}"))
+        .collect::<Vec<String>>();
+    assert_eq!(windows, ["ab"]);
+}
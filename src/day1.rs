@@ -1,20 +1,25 @@
+#[cfg(test)]
 use std::fs;
 
-fn solution(input: &str) -> usize {
-    input
-        .lines()
-        .map(|l| l.parse::<i32>().unwrap())
-        .map_windows(|[a, b]| b > a)
+use crate::{
+    compat::MapWindowsExt,
+    error::{self, AocError},
+    solver::Solver,
+};
+
+pub fn solution(depths: &[i32]) -> usize {
+    depths
+        .iter()
+        .map_array_windows(|[a, b]| b > a)
         .filter(|x| *x)
         .count()
 }
 
-fn solution2(input: &str) -> usize {
-    input
-        .lines()
-        .map(|l| l.parse::<i32>().unwrap())
-        .map_windows(|[a, b, c]| a + b + c)
-        .map_windows(|[a, b]| b > a)
+pub fn solution2(depths: &[i32]) -> usize {
+    depths
+        .iter()
+        .map_array_windows(|[a, b, c]| *a + *b + *c)
+        .map_array_windows(|[a, b]| b > a)
         .filter(|x| *x)
         .count()
 }
@@ -22,14 +27,35 @@ fn solution2(input: &str) -> usize {
 #[test]
 fn test_run() {
     let input = fs::read_to_string("src/inputs/aoc_1_sample.input").unwrap();
-    assert_eq!(solution(&input), 7);
-    assert_eq!(solution2(&input), 5);
+    let depths = Day1::parse(&input).unwrap();
+    assert_eq!(solution(&depths), 7);
+    assert_eq!(solution2(&depths), 5);
     let input = fs::read_to_string("src/inputs/aoc_1.input").unwrap();
-    assert_eq!(solution(&input), 1559);
-    assert_eq!(solution2(&input), 1600);
+    let depths = Day1::parse(&input).unwrap();
+    assert_eq!(solution(&depths), 1559);
+    assert_eq!(solution2(&depths), 1600);
+    assert_eq!(
+        Day1::parse("199\n2OO").unwrap_err().to_string(),
+        "line 2, column 1: expected a number, found '2OO'"
+    );
 }
 
-pub fn run() -> (String, String) {
-    let input = fs::read_to_string("src/inputs/aoc_1.input").unwrap();
-    (solution(&input).to_string(), solution2(&input).to_string())
+pub struct Day1;
+
+impl Solver for Day1 {
+    type Input<'a> = Vec<i32>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        error::lines(input, |l| error::number(l, l))
+    }
+
+    fn part1(depths: &Self::Input<'_>) -> usize {
+        solution(depths)
+    }
+
+    fn part2(depths: &Self::Input<'_>) -> usize {
+        solution2(depths)
+    }
 }
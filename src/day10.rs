@@ -1,6 +1,12 @@
 use std::collections::VecDeque;
+#[cfg(test)]
 use std::fs;
 
+use crate::{
+    error::{self, AocError},
+    solver::Solver,
+};
+
 fn score_char(c: char) -> u32 {
     match c {
         ')' => 3,
@@ -35,7 +41,7 @@ fn is_open(c: char) -> bool {
     matches!(c, '(' | '[' | '{' | '<')
 }
 
-fn score_line(l: &str) -> (u32, u64) {
+pub fn score_line(l: &str) -> (u32, u64) {
     let mut q: VecDeque<char> = VecDeque::new();
     let score_p1 = l
         .chars()
@@ -66,30 +72,72 @@ fn score_line(l: &str) -> (u32, u64) {
     }
 }
 
-fn solution(input: &str) -> (u32, u64) {
-    let scores: Vec<(u32, u64)> = input.lines().map(score_line).collect();
+pub fn solution(scores: &[(u32, u64)]) -> u32 {
+    scores.iter().map(|s| s.0).sum()
+}
+
+pub fn solution2(scores: &[(u32, u64)]) -> u64 {
     let mut scores_p2: Vec<u64> = scores
         .iter()
         .filter(|(_, p2)| *p2 != 0)
         .map(|(_, p2)| *p2)
         .collect();
     scores_p2.sort();
-    (
-        scores.iter().map(|s| s.0).sum(),
-        scores_p2[scores_p2.len() / 2],
-    )
+    scores_p2[scores_p2.len() / 2]
 }
 
 #[test]
 fn test_run() {
     let input = fs::read_to_string("src/inputs/aoc_10_sample.input").unwrap();
-    assert_eq!(solution(&input), (26397, 288957));
+    let scores = Day10::parse(&input).unwrap();
+    assert_eq!((solution(&scores), solution2(&scores)), (26397, 288957));
     let input = fs::read_to_string("src/inputs/aoc_10.input").unwrap();
-    assert_eq!(solution(&input), (266301, 3404870164));
+    let scores = Day10::parse(&input).unwrap();
+    assert_eq!(
+        (solution(&scores), solution2(&scores)),
+        (266301, 3404870164)
+    );
+    assert_eq!(
+        Day10::parse("[()]\n{(x)}").unwrap_err(),
+        AocError::new(3, "unexpected character 'x'").on_line(2)
+    );
+    assert_eq!(
+        Day10::parse("").unwrap_err(),
+        AocError::new(1, "expected an incomplete line")
+    );
+    assert!(Day10::parse("(]\n()").is_err());
 }
 
-pub fn run() -> (String, String) {
-    let input = fs::read_to_string("src/inputs/aoc_10.input").unwrap();
-    let (p1, p2) = solution(&input);
-    (p1.to_string(), p2.to_string())
+pub struct Day10;
+
+impl Solver for Day10 {
+    type Input<'a> = Vec<(u32, u64)>;
+    type Output1 = u32;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        let scores = error::lines(input, |l| {
+            match l.char_indices().find(|(_, c)| !"()[]{}<>".contains(*c)) {
+                Some((i, c)) => Err(AocError::at(
+                    l,
+                    &l[i..],
+                    format!("unexpected character '{c}'"),
+                )),
+                None => Ok(score_line(l)),
+            }
+        })?;
+        // Part 2 takes the middle score of the incomplete lines.
+        if scores.iter().all(|(_, p2)| *p2 == 0) {
+            return Err(AocError::new(1, "expected an incomplete line"));
+        }
+        Ok(scores)
+    }
+
+    fn part1(scores: &Self::Input<'_>) -> u32 {
+        solution(scores)
+    }
+
+    fn part2(scores: &Self::Input<'_>) -> u64 {
+        solution2(scores)
+    }
 }
@@ -1,107 +1,68 @@
+#[cfg(test)]
 use std::fs;
+use std::str::FromStr;
 
-use itertools::Itertools;
+use crate::{
+    error::{self, AocError},
+    grid::{self, Pos},
+    solver::Solver,
+};
 
-#[derive(Debug)]
-struct Grid {
-    g: [[u8; 10]; 10],
+const SIZE: usize = 10;
+
+#[derive(Debug, Clone)]
+pub struct Grid {
+    g: grid::Grid<u8>,
 }
 
-impl From<&str> for Grid {
-    fn from(s: &str) -> Self {
-        Self {
-            g: s.lines()
-                .take(10)
-                .map(|l| {
-                    l.chars()
-                        .take(10)
-                        .map(|c| c as u8 - 0x30)
-                        .collect::<Vec<u8>>()
-                        .try_into()
-                        .unwrap()
-                })
-                .collect::<Vec<[u8; 10]>>()
-                .try_into()
-                .unwrap(),
+impl FromStr for Grid {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, AocError> {
+        let rows = error::lines(s, |l| match error::digits(l)? {
+            row if row.len() == SIZE => Ok(row),
+            row => Err(AocError::new(row.len().min(SIZE) + 1, "expected 10 digits")),
+        })?;
+        if rows.len() != SIZE {
+            return Err(AocError::new(1, "expected 10 rows").on_line(rows.len().min(SIZE) + 1));
         }
+        Ok(Self {
+            g: grid::Grid::from_rows(rows)?,
+        })
     }
 }
 
 impl Grid {
-    fn get_surroundings(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
-        [
-            (0, -1),
-            (0, 1),
-            (-1, 0),
-            (1, 0),
-            (-1, 1),
-            (1, 1),
-            (-1, -1),
-            (1, -1),
-        ]
-        .iter()
-        .filter_map(move |(xd, yd)| {
-            let nx = x as i32 + xd;
-            let ny = y as i32 + yd;
-            if nx < 0
-                || ny < 0
-                || ny as usize >= self.g.len()
-                || nx as usize >= self.g[ny as usize].len()
-            {
-                None
-            } else {
-                Some((nx as usize, ny as usize))
-            }
-        })
-    }
-
-    fn get_flashed(&self, flashing: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    fn get_flashed(&self, flashing: &[Pos]) -> Vec<Pos> {
         flashing
             .iter()
-            .flat_map(|(x, y)| self.get_surroundings(*x, *y))
-            .collect::<Vec<(usize, usize)>>()
+            .flat_map(|p| self.g.neighbours8(*p))
+            .collect::<Vec<Pos>>()
     }
 
     fn increase_all(&mut self) {
-        self.g
-            .iter_mut()
-            .for_each(|row| row.iter_mut().for_each(|v| *v += 1));
+        self.g.values_mut().for_each(|v| *v += 1);
     }
 
     fn zero_flashed(&mut self) -> u64 {
         self.g
-            .iter_mut()
-            .map(|row| {
-                row.iter_mut()
-                    .map(|v| {
-                        if *v == 11 {
-                            *v = 0;
-                            1
-                        } else {
-                            0
-                        }
-                    })
-                    .sum::<u64>()
+            .values_mut()
+            .map(|v| {
+                if *v == 11 {
+                    *v = 0;
+                    1
+                } else {
+                    0
+                }
             })
             .sum()
     }
 
-    fn get10s(&self) -> Vec<(usize, usize)> {
+    fn get10s(&self) -> Vec<Pos> {
         self.g
             .iter()
-            .enumerate()
-            .flat_map(|(y, row)| {
-                row.iter().enumerate().filter_map(
-                    move |(x, v)| {
-                        if *v == 10 {
-                            Some((x, y))
-                        } else {
-                            None
-                        }
-                    },
-                )
-            })
-            .collect_vec()
+            .filter_map(|(p, v)| if *v == 10 { Some(p) } else { None })
+            .collect()
     }
 
     fn step(&mut self) -> u64 {
@@ -113,11 +74,11 @@ impl Grid {
             }
             let flashed = self.get_flashed(&tens);
             // Bump all 10s to 11, so they won't be flashing again.
-            tens.iter().for_each(|(x, y)| self.g[*y][*x] += 1);
+            tens.iter().for_each(|p| self.g[*p] += 1);
 
-            flashed.iter().for_each(|(x, y)| {
-                if self.g[*y][*x] <= 9 {
-                    self.g[*y][*x] += 1;
+            flashed.iter().for_each(|p| {
+                if self.g[*p] <= 9 {
+                    self.g[*p] += 1;
                 }
             })
         }
@@ -125,27 +86,53 @@ impl Grid {
     }
 }
 
-fn solution(input: &str) -> u64 {
-    let mut g = Grid::from(input);
+pub fn solution(g: &Grid) -> u64 {
+    let mut g = g.clone();
     (0..100).map(|_| g.step()).sum()
 }
 
-fn solution2(input: &str) -> u64 {
-    let mut g = Grid::from(input);
+pub fn solution2(g: &Grid) -> u64 {
+    let mut g = g.clone();
     (1..u64::MAX).find(|_| g.step() == 100).unwrap()
 }
 
 #[test]
 fn test_run() {
     let input = fs::read_to_string("src/inputs/aoc_11_sample.input").unwrap();
-    assert_eq!(solution(&input), 1656);
-    assert_eq!(solution2(&input), 195);
+    let g = Day11::parse(&input).unwrap();
+    assert_eq!(solution(&g), 1656);
+    assert_eq!(solution2(&g), 195);
     let input = fs::read_to_string("src/inputs/aoc_11.input").unwrap();
-    assert_eq!(solution(&input), 1649);
-    assert_eq!(solution2(&input), 256);
+    let g = Day11::parse(&input).unwrap();
+    assert_eq!(solution(&g), 1649);
+    assert_eq!(solution2(&g), 256);
+    let lines = input.lines().collect::<Vec<&str>>();
+    assert_eq!(
+        Day11::parse(&lines[..9].join("\n")).unwrap_err(),
+        AocError::new(1, "expected 10 rows").on_line(10)
+    );
+    assert_eq!(
+        Day11::parse(&format!("{}\n{}0", lines[..9].join("\n"), lines[9])).unwrap_err(),
+        AocError::new(11, "expected 10 digits").on_line(10)
+    );
 }
 
-pub fn run() -> (String, String) {
-    let input = fs::read_to_string("src/inputs/aoc_11.input").unwrap();
-    (solution(&input).to_string(), solution2(&input).to_string())
+pub struct Day11;
+
+impl Solver for Day11 {
+    type Input<'a> = Grid;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        input.parse()
+    }
+
+    fn part1(g: &Self::Input<'_>) -> u64 {
+        solution(g)
+    }
+
+    fn part2(g: &Self::Input<'_>) -> u64 {
+        solution2(g)
+    }
 }
@@ -1,10 +1,18 @@
+#[cfg(test)]
+use std::fs;
 use std::{
     collections::{HashMap, VecDeque},
-    fs, iter,
+    iter,
 };
 
 use itertools::Itertools;
 
+use crate::{
+    cancel,
+    error::{self, AocError},
+    solver::Solver,
+};
+
 #[derive(Debug)]
 struct Node {
     edges: Vec<usize>,
@@ -12,20 +20,27 @@ struct Node {
 }
 
 #[derive(Debug)]
-struct Graph<'a> {
+pub struct Graph<'a> {
     nodes: Vec<Node>,
     name_to_idx: HashMap<&'a str, usize>,
 }
 
-impl<'a> From<&'a str> for Graph<'a> {
-    fn from(s: &'a str) -> Self {
-        let capacity = s.lines().count();
+impl<'a> TryFrom<&'a str> for Graph<'a> {
+    type Error = AocError;
+
+    fn try_from(s: &'a str) -> Result<Self, AocError> {
+        let edges = error::lines(s, |l| match l.split_once('-') {
+            Some((a, b)) if !a.is_empty() && !b.is_empty() => Ok((a, b)),
+            _ => Err(AocError::new(
+                1,
+                format!("expected '<cave>-<cave>', found '{l}'"),
+            )),
+        })?;
         let mut g = Graph {
-            nodes: Vec::with_capacity(capacity),
-            name_to_idx: HashMap::with_capacity(capacity),
+            nodes: Vec::with_capacity(edges.len()),
+            name_to_idx: HashMap::with_capacity(edges.len()),
         };
-        s.lines().for_each(|l| {
-            let (a, b) = l.split_once('-').unwrap();
+        edges.into_iter().for_each(|(a, b)| {
             let i = g.nodes.len();
 
             let a_idx = if g.name_to_idx.contains_key(a) {
@@ -58,7 +73,13 @@ impl<'a> From<&'a str> for Graph<'a> {
             push_node(a, a_idx, b_idx);
             push_node(b, b_idx, a_idx);
         });
-        g
+        match ["start", "end"]
+            .iter()
+            .find(|n| !g.name_to_idx.contains_key(*n))
+        {
+            Some(n) => Err(AocError::new(1, format!("missing cave '{n}'"))),
+            None => Ok(g),
+        }
     }
 }
 
@@ -87,6 +108,7 @@ impl<'a> Graph<'a> {
         to_visit.push_back(vec![start; 1]);
         let mut found = 0u32;
         while !to_visit.is_empty() {
+            cancel::check();
             let current = to_visit.pop_front().unwrap();
             self.nodes[*current.last().unwrap()]
                 .edges
@@ -105,31 +127,58 @@ impl<'a> Graph<'a> {
     }
 }
 
-fn solution(input: &str) -> u32 {
-    Graph::from(input).bfs(Graph::is_dead_p1)
+pub fn solution(g: &Graph) -> u32 {
+    g.bfs(Graph::is_dead_p1)
 }
 
-fn solution2(input: &str) -> u32 {
-    Graph::from(input).bfs(Graph::is_dead_p2)
+pub fn solution2(g: &Graph) -> u32 {
+    g.bfs(Graph::is_dead_p2)
 }
 
 #[test]
 fn test_run() {
     let input = fs::read_to_string("src/inputs/aoc_12_sample1.input").unwrap();
-    assert_eq!(solution(&input), 10);
-    assert_eq!(solution2(&input), 36);
+    let g = Day12::parse(&input).unwrap();
+    assert_eq!(solution(&g), 10);
+    assert_eq!(solution2(&g), 36);
     let input = fs::read_to_string("src/inputs/aoc_12_sample2.input").unwrap();
-    assert_eq!(solution(&input), 19);
-    assert_eq!(solution2(&input), 103);
+    let g = Day12::parse(&input).unwrap();
+    assert_eq!(solution(&g), 19);
+    assert_eq!(solution2(&g), 103);
     let input = fs::read_to_string("src/inputs/aoc_12_sample3.input").unwrap();
-    assert_eq!(solution(&input), 226);
-    assert_eq!(solution2(&input), 3509);
+    let g = Day12::parse(&input).unwrap();
+    assert_eq!(solution(&g), 226);
+    assert_eq!(solution2(&g), 3509);
     let input = fs::read_to_string("src/inputs/aoc_12.input").unwrap();
-    assert_eq!(solution(&input), 4659);
-    assert_eq!(solution2(&input), 148962);
+    let g = Day12::parse(&input).unwrap();
+    assert_eq!(solution(&g), 4659);
+    assert_eq!(solution2(&g), 148962);
+    assert_eq!(
+        Day12::parse("start-A\nA-b\nb_end").unwrap_err(),
+        AocError::new(1, "expected '<cave>-<cave>', found 'b_end'").on_line(3)
+    );
+    assert_eq!(
+        Day12::parse("start-A\nA-b").unwrap_err(),
+        AocError::new(1, "missing cave 'end'")
+    );
 }
 
-pub fn run() -> (String, String) {
-    let input = fs::read_to_string("src/inputs/aoc_12.input").unwrap();
-    (solution(&input).to_string(), solution2(&input).to_string())
+pub struct Day12;
+
+impl Solver for Day12 {
+    type Input<'a> = Graph<'a>;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        Graph::try_from(input)
+    }
+
+    fn part1(g: &Self::Input<'_>) -> u32 {
+        solution(g)
+    }
+
+    fn part2(g: &Self::Input<'_>) -> u32 {
+        solution2(g)
+    }
 }
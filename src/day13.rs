@@ -1,4 +1,12 @@
-use std::{collections::HashSet, fs};
+#[cfg(test)]
+use std::fs;
+use std::{collections::HashSet, str::FromStr};
+
+use crate::{
+    error::{self, AocError},
+    grid::Grid,
+    solver::Solver,
+};
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 struct Point {
@@ -6,13 +14,17 @@ struct Point {
     y: usize,
 }
 
-impl From<&str> for Point {
-    fn from(s: &str) -> Self {
-        let (sx, sy) = s.split_once(',').unwrap();
-        Self {
-            x: str::parse(sx).unwrap(),
-            y: str::parse(sy).unwrap(),
-        }
+impl FromStr for Point {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, AocError> {
+        let (sx, sy) = s
+            .split_once(',')
+            .ok_or_else(|| AocError::new(1, format!("expected '<x>,<y>', found '{s}'")))?;
+        Ok(Self {
+            x: error::number(s, sx)?,
+            y: error::number(s, sy)?,
+        })
     }
 }
 
@@ -22,17 +34,20 @@ enum Fold {
     Y(usize),
 }
 
-impl From<&str> for Fold {
-    fn from(s: &str) -> Self {
+impl FromStr for Fold {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, AocError> {
         let (axis, v) = s
             .strip_prefix("fold along ")
-            .unwrap()
-            .split_once('=')
-            .unwrap();
+            .and_then(|f| f.split_once('='))
+            .ok_or_else(|| {
+                AocError::new(1, format!("expected 'fold along <axis>=<n>', found '{s}'"))
+            })?;
         match axis {
-            "x" => Fold::X(str::parse(v).unwrap()),
-            "y" => Fold::Y(str::parse(v).unwrap()),
-            _ => panic!("!nicht gut!"),
+            "x" => Ok(Fold::X(error::number(s, v)?)),
+            "y" => Ok(Fold::Y(error::number(s, v)?)),
+            _ => Err(AocError::at(s, axis, format!("unknown axis '{axis}'"))),
         }
     }
 }
@@ -43,94 +58,119 @@ enum Action {
     Fold(Fold),
 }
 
-impl From<&str> for Action {
-    fn from(s: &str) -> Self {
-        match s.chars().next().unwrap() {
-            'f' => Action::Fold(Fold::from(s)),
-            _ => Action::Point(Point::from(s)),
+impl FromStr for Action {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, AocError> {
+        if s.starts_with('f') {
+            s.parse().map(Action::Fold)
+        } else {
+            s.parse().map(Action::Point)
         }
     }
 }
 
+// Each row of the paper on a new line.
 fn points_stringify(points: &HashSet<Point>) -> String {
     let max_x = points.iter().map(|p| p.x).max().unwrap() + 1;
     let max_y = points.iter().map(|p| p.y).max().unwrap() + 1;
-    (0..max_y)
-        .flat_map(|y| {
-            std::iter::once('\n').chain((0..max_x).map(move |x| {
-                if points.contains(&Point { x, y }) {
-                    'X'
-                } else {
-                    ' '
-                }
-            }))
-        })
-        .collect::<String>()
+    let paper = Grid::from_fn(max_x, max_y, |(x, y)| points.contains(&Point { x, y }));
+    format!("\n{}", paper.render(|dot| if *dot { 'X' } else { ' ' }))
 }
 
-fn solution(input: &str) -> (usize, String) {
-    let (points, folds): (Vec<Action>, Vec<Action>) = input
-        .lines()
-        .filter_map(|l| {
+#[derive(Debug)]
+pub struct Manual {
+    points: HashSet<Point>,
+    folds: Vec<Fold>,
+}
+
+impl FromStr for Manual {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, AocError> {
+        let (points, folds): (Vec<Action>, Vec<Action>) = error::lines(s, |l| {
             if l.is_empty() {
-                None
+                Ok(None)
             } else {
-                Some(Action::from(l))
+                l.parse().map(Some)
             }
-        })
+        })?
+        .into_iter()
+        .flatten()
         .partition(|a| matches!(a, Action::Point(_)));
+        if points.is_empty() {
+            return Err(AocError::new(1, "expected a dot"));
+        }
+        if folds.is_empty() {
+            return Err(AocError::new(1, "expected a fold instruction"));
+        }
+        Ok(Self {
+            points: points
+                .into_iter()
+                .filter_map(|p| {
+                    if let Action::Point(pp) = p {
+                        Some(pp)
+                    } else {
+                        None
+                    }
+                })
+                .collect(),
+            folds: folds
+                .into_iter()
+                .filter_map(|f| {
+                    if let Action::Fold(ff) = f {
+                        Some(ff)
+                    } else {
+                        None
+                    }
+                })
+                .collect(),
+        })
+    }
+}
 
-    let mut after_first_fold_count = 0;
-    let mut points = points
+fn fold(points: &HashSet<Point>, f: &Fold) -> HashSet<Point> {
+    points
         .iter()
-        .filter_map(|p| {
-            if let Action::Point(pp) = p {
-                Some(*pp)
-            } else {
-                None
-            }
-        })
-        .collect::<HashSet<Point>>();
-    for f in folds {
-        if let Action::Fold(f) = f {
-            points = points
-                .iter()
-                .map(|p| match f {
-                    Fold::X(v) => {
-                        if p.x <= v {
-                            *p
-                        } else {
-                            Point {
-                                y: p.y,
-                                x: p.x - (p.x - v) * 2,
-                            }
-                        }
+        .map(|p| match *f {
+            Fold::X(v) => {
+                if p.x <= v {
+                    *p
+                } else {
+                    Point {
+                        y: p.y,
+                        x: p.x - (p.x - v) * 2,
                     }
-                    Fold::Y(v) => {
-                        if p.y <= v {
-                            *p
-                        } else {
-                            Point {
-                                x: p.x,
-                                y: p.y - (p.y - v) * 2,
-                            }
-                        }
+                }
+            }
+            Fold::Y(v) => {
+                if p.y <= v {
+                    *p
+                } else {
+                    Point {
+                        x: p.x,
+                        y: p.y - (p.y - v) * 2,
                     }
-                })
-                .collect::<HashSet<Point>>();
-            if after_first_fold_count == 0 {
-                after_first_fold_count = points.len();
+                }
             }
-        }
-    }
-    (after_first_fold_count, points_stringify(&points))
+        })
+        .collect()
+}
+
+pub fn solution(m: &Manual) -> usize {
+    fold(&m.points, &m.folds[0]).len()
+}
+
+pub fn solution2(m: &Manual) -> String {
+    points_stringify(&m.folds.iter().fold(m.points.clone(), |p, f| fold(&p, f)))
 }
 
 #[test]
 fn test_run() {
     let input = fs::read_to_string("src/inputs/aoc_13_sample.input").unwrap();
+    let m = Day13::parse(&input).unwrap();
     assert_eq!(
-        solution(&input),
+        (solution(&m), solution2(&m)),
         (
             17,
             r"
@@ -143,8 +183,9 @@ XXXXX"
         )
     );
     let input = fs::read_to_string("src/inputs/aoc_13.input").unwrap();
+    let m = Day13::parse(&input).unwrap();
     assert_eq!(
-        solution(&input),
+        (solution(&m), solution2(&m)),
         (
             785,
             r"
@@ -157,10 +198,41 @@ X     XX  X  X X  X  XX   XXX X  X X  X"
                 .to_string()
         )
     );
+    assert_eq!(
+        Day13::parse("6,10\n\nfold along z=7").unwrap_err(),
+        AocError::new(12, "unknown axis 'z'").on_line(3)
+    );
+    assert_eq!(
+        Day13::parse("6;10").unwrap_err(),
+        AocError::new(1, "expected '<x>,<y>', found '6;10'")
+    );
+    assert!(Day13::parse("fold along y").is_err());
+    assert_eq!(
+        Day13::parse("6,10\n0,14\n").unwrap_err(),
+        AocError::new(1, "expected a fold instruction")
+    );
+    assert_eq!(
+        Day13::parse("").unwrap_err(),
+        AocError::new(1, "expected a dot")
+    );
 }
 
-pub fn run() -> (String, String) {
-    let input = fs::read_to_string("src/inputs/aoc_13.input").unwrap();
-    let (p1, p2) = solution(&input);
-    (p1.to_string(), p2)
+pub struct Day13;
+
+impl Solver for Day13 {
+    type Input<'a> = Manual;
+    type Output1 = usize;
+    type Output2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        input.parse()
+    }
+
+    fn part1(m: &Self::Input<'_>) -> usize {
+        solution(m)
+    }
+
+    fn part2(m: &Self::Input<'_>) -> String {
+        solution2(m)
+    }
 }
@@ -1,6 +1,9 @@
 use std::collections::HashMap;
+#[cfg(test)]
 use std::fs;
-use std::sync::Mutex;
+use std::str::FromStr;
+
+use crate::{compat::MapWindowsExt, error::AocError, solver::Solver};
 
 type Pair = [char; 2];
 
@@ -18,17 +21,54 @@ struct PairLevel {
 
 type CharCountMap = HashMap<char, u64>;
 type RuleMap = HashMap<Pair, char>;
+// The counts inserted below each pair at each level, for a single solve.
+type Cache = HashMap<PairLevel, CharCountMap>;
+
+impl FromStr for PairRule {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, AocError> {
+        let err = || AocError::new(1, format!("expected '<pair> -> <element>', found '{s}'"));
+        let (pair, insert) = s.split_once(" -> ").ok_or_else(err)?;
+        match (
+            &pair.chars().collect::<Vec<char>>()[..],
+            &insert.chars().collect::<Vec<char>>()[..],
+        ) {
+            (&[a, b], &[c]) => Ok(Self { p: [a, b], i: c }),
+            _ => Err(err()),
+        }
+    }
+}
 
-lazy_static::lazy_static! {
-    static ref VALUECACHE: Mutex<HashMap<PairLevel, CharCountMap>> = Mutex::new(HashMap::new());
+#[derive(Debug)]
+pub struct Polymer {
+    template: String,
+    rules: RuleMap,
 }
 
-impl From<&str> for PairRule {
-    fn from(s: &str) -> Self {
-        let a = s.chars().nth(0).unwrap();
-        let b = s.chars().nth(1).unwrap();
-        let c = s.chars().nth(6).unwrap();
-        Self { p: [a, b], i: c }
+impl FromStr for Polymer {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, AocError> {
+        let mut lines = s.lines();
+        let template = lines.next().unwrap_or_default().to_string();
+        if template.is_empty() {
+            return Err(AocError::new(1, "expected a polymer template"));
+        }
+        if lines.next().is_some_and(|l| !l.is_empty()) {
+            return Err(AocError::new(1, "expected a blank line").on_line(2));
+        }
+        Ok(Self {
+            template,
+            rules: lines
+                .enumerate()
+                .map(|(n, l)| {
+                    l.parse::<PairRule>()
+                        .map(|pr| (pr.p, pr.i))
+                        .map_err(|e| e.on_line(n + 3))
+                })
+                .collect::<Result<RuleMap, AocError>>()?,
+        })
     }
 }
 
@@ -36,8 +76,14 @@ fn merge_maps(m1: &mut CharCountMap, m2: &CharCountMap) {
     m2.iter().for_each(|(k, v)| *m1.entry(*k).or_default() += v);
 }
 
-fn simulate(level: u32, max_level: u32, p: &Pair, rules: &RuleMap) -> CharCountMap {
-    if let Some(r) = VALUECACHE.lock().unwrap().get(&PairLevel { p: *p, level }) {
+fn simulate(
+    level: u32,
+    max_level: u32,
+    p: &Pair,
+    rules: &RuleMap,
+    cache: &mut Cache,
+) -> CharCountMap {
+    if let Some(r) = cache.get(&PairLevel { p: *p, level }) {
         return r.clone();
     }
     let rule = rules.get(p).unwrap();
@@ -45,29 +91,21 @@ fn simulate(level: u32, max_level: u32, p: &Pair, rules: &RuleMap) -> CharCountM
     if level != max_level {
         merge_maps(
             &mut m1,
-            &simulate(level + 1, max_level, &[p[0], *rule], rules),
+            &simulate(level + 1, max_level, &[p[0], *rule], rules, cache),
         );
         merge_maps(
             &mut m1,
-            &simulate(level + 1, max_level, &[*rule, p[1]], rules),
+            &simulate(level + 1, max_level, &[*rule, p[1]], rules, cache),
         );
     }
-    VALUECACHE
-        .lock()
-        .unwrap()
-        .insert(PairLevel { p: *p, level }, m1.clone());
+    cache.insert(PairLevel { p: *p, level }, m1.clone());
     m1
 }
 
-fn solution(input: &str, max_steps: u32) -> u64 {
-    VALUECACHE.lock().unwrap().clear();
-    let initial_polymer = input.lines().next().unwrap().chars().collect::<String>();
-    let rules = input
-        .lines()
-        .skip(2)
-        .map(PairRule::from)
-        .map(|pr| (pr.p, pr.i))
-        .collect::<RuleMap>();
+pub fn solution(polymer: &Polymer, max_steps: u32) -> u64 {
+    let mut cache = Cache::new();
+    let initial_polymer = &polymer.template;
+    let rules = &polymer.rules;
 
     let mut cm = CharCountMap::new();
     initial_polymer
@@ -76,9 +114,9 @@ fn solution(input: &str, max_steps: u32) -> u64 {
 
     initial_polymer
         .chars()
-        .map_windows(|[a, b]| [*a, *b])
+        .map_array_windows(|[a, b]| [*a, *b])
         .for_each(|p| {
-            merge_maps(&mut cm, &simulate(1, max_steps, &p, &rules));
+            merge_maps(&mut cm, &simulate(1, max_steps, &p, rules, &mut cache));
         });
     cm.values().max().unwrap() - cm.values().min().unwrap()
 }
@@ -86,17 +124,37 @@ fn solution(input: &str, max_steps: u32) -> u64 {
 #[test]
 fn test_run() {
     let input = fs::read_to_string("src/inputs/aoc_14_sample.input").unwrap();
-    assert_eq!(solution(&input, 10), 1588);
-    assert_eq!(solution(&input, 40), 2188189693529);
+    let polymer = Day14::parse(&input).unwrap();
+    assert_eq!(solution(&polymer, 10), 1588);
+    assert_eq!(solution(&polymer, 40), 2188189693529);
     let input = fs::read_to_string("src/inputs/aoc_14.input").unwrap();
-    assert_eq!(solution(&input, 10), 3555);
-    assert_eq!(solution(&input, 40), 4439442043739);
+    let polymer = Day14::parse(&input).unwrap();
+    assert_eq!(solution(&polymer, 10), 3555);
+    assert_eq!(solution(&polymer, 40), 4439442043739);
+    assert_eq!(
+        Day14::parse("NNCB\n\nCH -> B\nHH => N").unwrap_err(),
+        AocError::new(1, "expected '<pair> -> <element>', found 'HH => N'").on_line(4)
+    );
+    assert!(Day14::parse("NNCB\nCH -> B").is_err());
+    assert!(Day14::parse("NNCB\n\nCHH -> B").is_err());
 }
 
-pub fn run() -> (String, String) {
-    let input = fs::read_to_string("src/inputs/aoc_14.input").unwrap();
-    (
-        solution(&input, 10).to_string(),
-        solution(&input, 40).to_string(),
-    )
+pub struct Day14;
+
+impl Solver for Day14 {
+    type Input<'a> = Polymer;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        input.parse()
+    }
+
+    fn part1(polymer: &Self::Input<'_>) -> u64 {
+        solution(polymer, 10)
+    }
+
+    fn part2(polymer: &Self::Input<'_>) -> u64 {
+        solution(polymer, 40)
+    }
 }
@@ -1,7 +1,12 @@
 use bitreader::BitReader;
+use hex::FromHexError;
 use itertools::FoldWhile;
 use itertools::Itertools;
-use std::{fs, iter};
+#[cfg(test)]
+use std::fs;
+use std::{iter, str::FromStr};
+
+use crate::{error::AocError, solver::Solver};
 
 fn handle_operator(op: u8, vals: &[u64]) -> u64 {
     match op {
@@ -12,79 +17,199 @@ fn handle_operator(op: u8, vals: &[u64]) -> u64 {
         5 => (vals[0] > vals[1]) as u64,
         6 => (vals[0] < vals[1]) as u64,
         7 => (vals[0] == vals[1]) as u64,
-        _ => panic!("!kaputt!"),
+        _ => unreachable!("type 4 packets are literals"),
     }
 }
 
-fn handle_literal(br: &mut BitReader) -> u64 {
+// Hex digit of the transmission the reader is at.
+fn column(br: &BitReader) -> usize {
+    br.position() as usize / 4 + 1
+}
+
+fn read(br: &mut BitReader, bits: u8) -> Result<u64, AocError> {
+    let column = column(br);
+    br.read_u64(bits)
+        .map_err(|_| AocError::new(column, "unexpected end of transmission"))
+}
+
+fn handle_literal(br: &mut BitReader) -> Result<u64, AocError> {
     iter::repeat(0)
-        .fold_while((1, 0u64), |(last_group, val), _| {
-            if last_group != 0 {
-                FoldWhile::Continue((br.read_u8(1).unwrap(), (val << 4) | br.read_u64(4).unwrap()))
-            } else {
-                FoldWhile::Done((0, val))
+        .fold_while(Ok((1, 0u64)), |acc, _| match acc {
+            Ok((last_group, val)) if last_group != 0 => {
+                FoldWhile::Continue(read(br, 1).and_then(|g| Ok((g, (val << 4) | read(br, 4)?))))
             }
+            done => FoldWhile::Done(done),
         })
         .into_inner()
-        .1
+        .map(|(_, val)| val)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Packet {
+    Literal {
+        version: u32,
+        value: u64,
+    },
+    Operator {
+        version: u32,
+        op: u8,
+        packets: Vec<Packet>,
+    },
 }
 
-fn handle_packets_by_bit_size(br: &mut BitReader, cver: u32, op: u8) -> (u32, u64) {
-    let max_pos = br.read_u16(15).unwrap() as u64 + br.position();
-    let (ver, vals) = iter::repeat(0)
-        .fold_while((0u32, Vec::new()), |(ver, mut vals), _| {
-            if br.position() == max_pos {
-                FoldWhile::Done((ver, vals))
-            } else {
-                let (pver, pval) = parse_packet(br);
-                vals.push(pval);
-                FoldWhile::Continue((ver + pver, vals))
+impl Packet {
+    pub fn version_sum(&self) -> u32 {
+        match self {
+            Packet::Literal { version, .. } => *version,
+            Packet::Operator {
+                version, packets, ..
+            } => version + packets.iter().map(Packet::version_sum).sum::<u32>(),
+        }
+    }
+
+    pub fn value(&self) -> u64 {
+        match self {
+            Packet::Literal { value, .. } => *value,
+            Packet::Operator { op, packets, .. } => handle_operator(
+                *op,
+                &packets.iter().map(Packet::value).collect::<Vec<u64>>(),
+            ),
+        }
+    }
+}
+
+fn handle_packets_by_bit_size(br: &mut BitReader) -> Result<Vec<Packet>, AocError> {
+    let max_pos = read(br, 15)? + br.position();
+    iter::repeat(0)
+        .fold_while(Ok(Vec::new()), |acc, _| match acc {
+            Ok(mut packets) if br.position() < max_pos => {
+                FoldWhile::Continue(parse_packet(br).map(|p| {
+                    packets.push(p);
+                    packets
+                }))
             }
+            done => FoldWhile::Done(done),
+        })
+        .into_inner()
+        .and_then(|packets| match br.position() == max_pos {
+            true => Ok(packets),
+            false => Err(AocError::new(column(br), "sub-packets exceed their length")),
         })
-        .into_inner();
-    (cver + ver, handle_operator(op, &vals))
 }
 
-fn handle_packets_by_cnt(br: &mut BitReader, cver: u32, op: u8) -> (u32, u64) {
-    let (ver, vals) =
-        (0..br.read_u16(11).unwrap()).fold((0u32, Vec::new()), |(ver, mut vals), _| {
-            let (pver, pval) = parse_packet(br);
-            vals.push(pval);
-            (ver + pver, vals)
-        });
-    (cver + ver, handle_operator(op, &vals))
+fn handle_packets_by_cnt(br: &mut BitReader) -> Result<Vec<Packet>, AocError> {
+    (0..read(br, 11)?).map(|_| parse_packet(br)).collect()
 }
 
-fn parse_packet(br: &mut BitReader) -> (u32, u64) {
-    let ver = br.read_u32(3).unwrap();
-    match br.read_u8(3).unwrap() {
-        4 => (ver, handle_literal(br)),
-        op => match br.read_u8(1).unwrap() {
-            0 => handle_packets_by_bit_size(br, ver, op),
-            1 => handle_packets_by_cnt(br, ver, op),
-            _ => panic!("!kaputt!"),
-        },
+fn parse_packet(br: &mut BitReader) -> Result<Packet, AocError> {
+    let start = column(br);
+    let version = read(br, 3)? as u32;
+    match read(br, 3)? as u8 {
+        4 => Ok(Packet::Literal {
+            version,
+            value: handle_literal(br)?,
+        }),
+        op => {
+            let packets = match read(br, 1)? {
+                0 => handle_packets_by_bit_size(br)?,
+                _ => handle_packets_by_cnt(br)?,
+            };
+            match (op, packets.len()) {
+                (5..=7, n) if n != 2 => Err(AocError::new(
+                    start,
+                    format!("operator {op} expects 2 sub-packets, found {n}"),
+                )),
+                (2 | 3, 0) => Err(AocError::new(
+                    start,
+                    format!("operator {op} expects sub-packets"),
+                )),
+                _ => Ok(Packet::Operator {
+                    version,
+                    op,
+                    packets,
+                }),
+            }
+        }
+    }
+}
+
+impl FromStr for Packet {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, AocError> {
+        let data = hex::decode(s).map_err(|e| match e {
+            FromHexError::InvalidHexCharacter { c, index } => AocError::new(
+                s.get(..index).map_or(index, |p| p.chars().count()) + 1,
+                format!("invalid hex digit '{}'", c.escape_default()),
+            ),
+            e => AocError::new(s.chars().count(), e.to_string()),
+        })?;
+        parse_packet(&mut BitReader::new(&data))
     }
 }
 
-fn solution(input: &str) -> (u32, u64) {
-    let data = hex::decode(input).unwrap();
-    let mut br = BitReader::new(&data);
-    parse_packet(&mut br)
+pub fn solution(p: &Packet) -> u32 {
+    p.version_sum()
+}
+
+pub fn solution2(p: &Packet) -> u64 {
+    p.value()
 }
 
 #[test]
 fn test_run() {
-    assert_eq!(solution("8A004A801A8002F478"), (16, 15));
-    assert_eq!(solution("620080001611562C8802118E34"), (12, 46));
-    assert_eq!(solution("C0015000016115A2E0802F182340"), (23, 46));
-    assert_eq!(solution("A0016C880162017C3686B18A3D4780"), (31, 54));
+    let solve = |s: &str| {
+        let p = Day16::parse(s).unwrap();
+        (solution(&p), solution2(&p))
+    };
+    assert_eq!(
+        Day16::parse("D2FE28").unwrap(),
+        Packet::Literal {
+            version: 6,
+            value: 2021
+        }
+    );
+    assert_eq!(solve("8A004A801A8002F478"), (16, 15));
+    assert_eq!(solve("620080001611562C8802118E34"), (12, 46));
+    assert_eq!(solve("C0015000016115A2E0802F182340"), (23, 46));
+    assert_eq!(solve("A0016C880162017C3686B18A3D4780"), (31, 54));
     let input = fs::read_to_string("src/inputs/aoc_16.input").unwrap();
-    assert_eq!(solution(&input), (873, 402817863665));
+    assert_eq!(solve(&input), (873, 402817863665));
+    assert_eq!(
+        Day16::parse("D2FE2").unwrap_err(),
+        AocError::new(5, "Odd number of digits")
+    );
+    assert_eq!(
+        Day16::parse("D2FX28").unwrap_err(),
+        AocError::new(4, "invalid hex digit 'X'")
+    );
+    assert_eq!(
+        Day16::parse("D2FE").unwrap_err(),
+        AocError::new(5, "unexpected end of transmission")
+    );
+    // A comparison with a single literal sub-packet.
+    assert_eq!(
+        Day16::parse("D6007408").unwrap_err(),
+        AocError::new(1, "operator 5 expects 2 sub-packets, found 1")
+    );
 }
 
-pub fn run() -> (String, String) {
-    let input = fs::read_to_string("src/inputs/aoc_16.input").unwrap();
-    let (p1, p2) = solution(&input);
-    (p1.to_string(), p2.to_string())
+pub struct Day16;
+
+impl Solver for Day16 {
+    type Input<'a> = Packet;
+    type Output1 = u32;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        input.parse()
+    }
+
+    fn part1(p: &Self::Input<'_>) -> u32 {
+        solution(p)
+    }
+
+    fn part2(p: &Self::Input<'_>) -> u64 {
+        solution2(p)
+    }
 }
@@ -2,10 +2,17 @@ use itertools::FoldWhile;
 use itertools::Itertools;
 use lazy_static::lazy_static;
 use regex::Regex;
-use std::iter;
+#[cfg(test)]
+use std::fs;
+use std::{iter, str::FromStr};
+
+use crate::{
+    error::{self, AocError},
+    solver::Solver,
+};
 
 #[derive(Debug)]
-struct Bucket {
+pub struct Bucket {
     x_min: i32,
     x_max: i32,
     y_min: i32,
@@ -41,44 +48,83 @@ impl Bucket {
     }
 }
 
-impl From<&str> for Bucket {
-    fn from(s: &str) -> Self {
+impl FromStr for Bucket {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, AocError> {
         lazy_static! {
             static ref RE: Regex =
                 Regex::new(r"target area: x=(-?\d+)\.\.(-?\d+), y=(-?\d+)\.\.(-?\d+)").unwrap();
         }
-        let caps = RE.captures(s).unwrap();
-        Self {
-            x_min: (caps[1]).parse::<i32>().unwrap(),
-            x_max: (caps[2]).parse::<i32>().unwrap(),
-            y_min: (caps[3]).parse::<i32>().unwrap(),
-            y_max: (caps[4]).parse::<i32>().unwrap(),
+        let caps = RE.captures(s).ok_or_else(|| {
+            AocError::new(1, "expected 'target area: x=<min>..<max>, y=<min>..<max>'")
+        })?;
+        let b = Self {
+            x_min: error::number(s, &caps[1])?,
+            x_max: error::number(s, &caps[2])?,
+            y_min: error::number(s, &caps[3])?,
+            y_max: error::number(s, &caps[4])?,
+        };
+        if b.x_min > b.x_max || b.y_min > b.y_max || b.x_min <= 0 || b.y_max >= 0 {
+            return Err(AocError::at(
+                s,
+                caps.get(1).unwrap().as_str(),
+                "expected a target area right of and below the launcher",
+            ));
         }
+        Ok(b)
     }
 }
 
-fn solution(input: &str) -> (i32, usize) {
-    let b = Bucket::from(input);
+pub fn solution(b: &Bucket) -> i32 {
+    let p1_max = std::cmp::max(b.y_min.abs(), b.y_max.abs());
+    (p1_max - 1) * p1_max / 2
+}
+
+pub fn solution2(b: &Bucket) -> usize {
     let min_x_velocity = ((-1.0 + ((1 + 4 * 2 * b.x_min) as f32).sqrt()) / 2.0).ceil() as i32;
-    let p2 = b.area()
+    b.area()
         + (min_x_velocity..b.x_min)
             .map(|x| (b.y_max..100).rev().filter(|y| b.simulate((x, *y))).count())
-            .sum::<usize>();
-    let p1_max = std::cmp::max(b.y_min.abs(), b.y_max.abs());
-    ((p1_max - 1) * p1_max / 2, p2)
+            .sum::<usize>()
 }
 
 #[test]
 fn test_run() {
-    assert_eq!(solution("target area: x=20..30, y=-10..-5"), (45, 112));
+    let b = Day17::parse("target area: x=20..30, y=-10..-5").unwrap();
+    assert_eq!((solution(&b), solution2(&b)), (45, 112));
+    let b = Day17::parse("target area: x=137..171, y=-98..-73").unwrap();
+    assert_eq!((solution(&b), solution2(&b)), (4753, 1546));
+    let input = fs::read_to_string("src/inputs/aoc_17_sample.input").unwrap();
+    let b = Day17::parse(&input).unwrap();
+    assert_eq!((solution(&b), solution2(&b)), (45, 112));
+    let input = fs::read_to_string("src/inputs/aoc_17.input").unwrap();
+    let b = Day17::parse(&input).unwrap();
+    assert_eq!((solution(&b), solution2(&b)), (4753, 1546));
     assert_eq!(
-        solution("target area: x=137..171, y=-98..-73"),
-        (4753, 1546)
+        Day17::parse("target area: x=20..30, y=-10..99999999999").unwrap_err(),
+        AocError::new(31, "expected a number, found '99999999999'")
     );
+    assert!(Day17::parse("target area: x=20..30").is_err());
+    assert!(Day17::parse("target area: x=30..20, y=-10..-5").is_err());
 }
 
-pub fn run() -> (String, String) {
-    let input = "target area: x=137..171, y=-98..-73";
-    let (p1, p2) = solution(input);
-    (p1.to_string(), p2.to_string())
+pub struct Day17;
+
+impl Solver for Day17 {
+    type Input<'a> = Bucket;
+    type Output1 = i32;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        input.parse()
+    }
+
+    fn part1(b: &Self::Input<'_>) -> i32 {
+        solution(b)
+    }
+
+    fn part2(b: &Self::Input<'_>) -> usize {
+        solution2(b)
+    }
 }
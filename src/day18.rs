@@ -1,4 +1,12 @@
-use std::{fs, iter};
+#[cfg(test)]
+use std::fs;
+use std::{fmt, iter, str::FromStr};
+
+use crate::{
+    cancel,
+    error::{self, AocError},
+    solver::Solver,
+};
 
 #[derive(Debug, Clone, Copy)]
 enum Token {
@@ -9,38 +17,67 @@ enum Token {
 }
 
 #[derive(Debug, Clone)]
-struct SnailFishNumber {
+pub struct SnailFishNumber {
     q: Vec<Token>,
 }
 
-impl From<&str> for SnailFishNumber {
-    fn from(value: &str) -> Self {
-        Self {
-            q: value
-                .chars()
-                .map(|c| match c {
-                    '[' => Token::Open,
-                    ']' => Token::Close,
-                    ',' => Token::Comma,
-                    '0'..='9' => Token::Value(c as u32 - 0x30),
-                    _ => panic!("invalid character {c}"),
-                })
-                .collect(),
+// Checks that `q[i..]` starts with `t`, returns the index after it.
+fn expect(q: &[Token], i: usize, t: char) -> Result<usize, AocError> {
+    match (q.get(i), t) {
+        (Some(Token::Comma), ',') | (Some(Token::Close), ']') => Ok(i + 1),
+        _ => Err(AocError::new(i + 1, format!("expected '{t}'"))),
+    }
+}
+
+// Checks that `q[i..]` starts with a regular number or a pair, returns the index after it.
+fn check_element(q: &[Token], i: usize) -> Result<usize, AocError> {
+    match q.get(i) {
+        Some(Token::Value(_)) => Ok(i + 1),
+        Some(Token::Open) => {
+            let i = check_element(q, i + 1)?;
+            let i = check_element(q, expect(q, i, ',')?)?;
+            expect(q, i, ']')
         }
+        _ => Err(AocError::new(i + 1, "expected '[' or a digit")),
     }
 }
 
-impl ToString for SnailFishNumber {
-    fn to_string(&self) -> String {
-        self.q
-            .iter()
-            .map(|t| match t {
-                Token::Open => "[".to_string(),
-                Token::Close => "]".to_string(),
-                Token::Comma => ",".to_string(),
-                Token::Value(v) => v.to_string(),
+impl FromStr for SnailFishNumber {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, AocError> {
+        let q = s
+            .chars()
+            .enumerate()
+            .map(|(i, c)| match c {
+                '[' => Ok(Token::Open),
+                ']' => Ok(Token::Close),
+                ',' => Ok(Token::Comma),
+                '0'..='9' => Ok(Token::Value(c as u32 - 0x30)),
+                _ => Err(AocError::new(i + 1, format!("unexpected character '{c}'"))),
             })
-            .collect()
+            .collect::<Result<Vec<Token>, AocError>>()?;
+        if !matches!(q.first(), Some(Token::Open)) {
+            return Err(AocError::new(1, "expected '['"));
+        }
+        match check_element(&q, 0)? {
+            end if end == q.len() => Ok(Self { q }),
+            end => Err(AocError::new(
+                end + 1,
+                "unexpected characters after the number",
+            )),
+        }
+    }
+}
+
+impl fmt::Display for SnailFishNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.q.iter().try_for_each(|t| match t {
+            Token::Open => write!(f, "["),
+            Token::Close => write!(f, "]"),
+            Token::Comma => write!(f, ","),
+            Token::Value(v) => write!(f, "{v}"),
+        })
     }
 }
 
@@ -49,7 +86,7 @@ impl SnailFishNumber {
         if let Token::Value(rval) = q.pop().unwrap() {
             rval
         } else {
-            panic!("why u no Token::Value ლ(ಠ益ಠლ)")
+            unreachable!("numbers are validated when parsed")
         }
     }
 
@@ -178,12 +215,12 @@ impl SnailFishNumber {
             self.q = other.q.clone();
         } else {
             self.q = iter::once(&Token::Open)
-            .chain(self.q.iter())
-            .chain(iter::once(&Token::Comma))
-            .chain(other.q.iter())
-            .chain(iter::once(&Token::Close))
-            .cloned()
-            .collect::<Vec<Token>>();
+                .chain(self.q.iter())
+                .chain(iter::once(&Token::Comma))
+                .chain(other.q.iter())
+                .chain(iter::once(&Token::Close))
+                .cloned()
+                .collect::<Vec<Token>>();
         }
         self
     }
@@ -207,26 +244,21 @@ impl SnailFishNumber {
     }
 }
 
-fn solution(input: &str) -> u32 {
-    input
-        .lines()
-        .map(SnailFishNumber::from)
-        .fold(SnailFishNumber::from(""), |mut acc, v| {
-            acc.add(&v).normalize().to_owned()
+pub fn solution(ns: &[SnailFishNumber]) -> u32 {
+    ns.iter()
+        .fold(SnailFishNumber { q: Vec::new() }, |mut acc, v| {
+            acc.add(v).normalize().to_owned()
         })
         .normalize()
         .magnitude()
 }
 
-fn solution2(input: &str) -> u32 {
-    let ns = input
-        .lines()
-        .map(SnailFishNumber::from)
-        .collect::<Vec<SnailFishNumber>>();
+pub fn solution2(ns: &[SnailFishNumber]) -> u32 {
     (0..ns.len())
         .map(|i| {
             (0..ns.len())
                 .map(|j| {
+                    cancel::check();
                     if i != j {
                         ns[i].clone().add(&ns[j]).normalize().magnitude()
                     } else {
@@ -240,12 +272,40 @@ fn solution2(input: &str) -> u32 {
         .unwrap()
 }
 
+#[cfg(test)]
+fn number(s: &str) -> SnailFishNumber {
+    s.parse().unwrap()
+}
+
+#[test]
+fn test_parse() {
+    assert_eq!(number("[[1,2],3]").to_string(), "[[1,2],3]");
+    let err = |s: &str| s.parse::<SnailFishNumber>().unwrap_err();
+    assert_eq!(err("[1,2"), AocError::new(5, "expected ']'"));
+    assert_eq!(err("[1;2]"), AocError::new(3, "unexpected character ';'"));
+    assert_eq!(err("[[1,2]3]"), AocError::new(7, "expected ','"));
+    assert_eq!(
+        err("[1,2]]"),
+        AocError::new(6, "unexpected characters after the number")
+    );
+    assert_eq!(err("7"), AocError::new(1, "expected '['"));
+    assert_eq!(
+        Day18::parse("[1,2]\n[1,]").unwrap_err(),
+        AocError::new(4, "expected '[' or a digit").on_line(2)
+    );
+    assert_eq!(
+        Day18::parse("").unwrap_err(),
+        AocError::new(1, "expected at least two snailfish numbers")
+    );
+    assert!(Day18::parse("[1,2]").is_err());
+}
+
 #[test]
 fn test_magnitude() {
-    assert_eq!(SnailFishNumber::from("[9,1]").magnitude(), 29);
-    assert_eq!(SnailFishNumber::from("[[9,1],[1,9]]").magnitude(), 129);
+    assert_eq!(number("[9,1]").magnitude(), 29);
+    assert_eq!(number("[[9,1],[1,9]]").magnitude(), 129);
     assert_eq!(
-        SnailFishNumber::from("[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]").magnitude(),
+        number("[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]").magnitude(),
         3488
     );
 }
@@ -253,35 +313,29 @@ fn test_magnitude() {
 #[test]
 fn test_explode() {
     assert_eq!(
-        SnailFishNumber::from("[[[[[9,8],1],2],3],4]")
-            .explode()
-            .to_string(),
+        number("[[[[[9,8],1],2],3],4]").explode().to_string(),
         "[[[[0,9],2],3],4]"
     );
 
     assert_eq!(
-        SnailFishNumber::from("[7,[6,[5,[4,[3,2]]]]]")
-            .explode()
-            .to_string(),
+        number("[7,[6,[5,[4,[3,2]]]]]").explode().to_string(),
         "[7,[6,[5,[7,0]]]]"
     );
 
     assert_eq!(
-        SnailFishNumber::from("[[6,[5,[4,[3,2]]]],1]")
-            .explode()
-            .to_string(),
+        number("[[6,[5,[4,[3,2]]]],1]").explode().to_string(),
         "[[6,[5,[7,0]]],3]"
     );
 
     assert_eq!(
-        SnailFishNumber::from("[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]")
+        number("[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]")
             .explode()
             .to_string(),
         "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]"
     );
 
     assert_eq!(
-        SnailFishNumber::from("[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]")
+        number("[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]")
             .explode()
             .to_string(),
         "[[3,[2,[8,0]]],[9,[5,[7,0]]]]"
@@ -291,10 +345,10 @@ fn test_explode() {
 #[test]
 fn test_add() {
     assert_eq!(
-        SnailFishNumber::from("[1,1]")
-            .add(&SnailFishNumber::from("[2,2]"))
-            .add(&SnailFishNumber::from("[3,3]"))
-            .add(&SnailFishNumber::from("[4,4]"))
+        number("[1,1]")
+            .add(&number("[2,2]"))
+            .add(&number("[3,3]"))
+            .add(&number("[4,4]"))
             .to_string(),
         "[[[[1,1],[2,2]],[3,3]],[4,4]]"
     );
@@ -303,8 +357,8 @@ fn test_add() {
 #[test]
 fn test_add_normalize() {
     assert_eq!(
-        SnailFishNumber::from("[[[[4,3],4],4],[7,[[8,4],9]]]")
-            .add(&SnailFishNumber::from("[1,1]"))
+        number("[[[[4,3],4],4],[7,[[8,4],9]]]")
+            .add(&number("[1,1]"))
             .normalize()
             .to_string(),
         "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]"
@@ -314,14 +368,36 @@ fn test_add_normalize() {
 #[test]
 fn test_run() {
     let input = fs::read_to_string("src/inputs/aoc_18_sample.input").unwrap();
-    assert_eq!(solution(&input), 4140);
-    assert_eq!(solution2(&input), 3993);
+    let ns = Day18::parse(&input).unwrap();
+    assert_eq!(solution(&ns), 4140);
+    assert_eq!(solution2(&ns), 3993);
     let input = fs::read_to_string("src/inputs/aoc_18.input").unwrap();
-    assert_eq!(solution(&input), 3524);
-    assert_eq!(solution2(&input), 4656);
+    let ns = Day18::parse(&input).unwrap();
+    assert_eq!(solution(&ns), 3524);
+    assert_eq!(solution2(&ns), 4656);
 }
 
-pub fn run() -> (String, String) {
-    let input = fs::read_to_string("src/inputs/aoc_18.input").unwrap();
-    (solution(&input).to_string(), solution2(&input).to_string())
+pub struct Day18;
+
+impl Solver for Day18 {
+    type Input<'a> = Vec<SnailFishNumber>;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        let ns = error::parse_lines(input)?;
+        // Part 2 adds two different numbers.
+        if ns.len() < 2 {
+            return Err(AocError::new(1, "expected at least two snailfish numbers"));
+        }
+        Ok(ns)
+    }
+
+    fn part1(ns: &Self::Input<'_>) -> u32 {
+        solution(ns)
+    }
+
+    fn part2(ns: &Self::Input<'_>) -> u32 {
+        solution2(ns)
+    }
 }
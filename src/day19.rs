@@ -0,0 +1,241 @@
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
+#[cfg(test)]
+use std::fs;
+use std::str::FromStr;
+
+use crate::{
+    cancel,
+    error::{self, AocError},
+    geometry::{Point, Rotation, Vector},
+    input,
+    solver::Solver,
+};
+
+// Scanners whose ranges overlap see at least this many of the same beacons.
+const OVERLAP: usize = 12;
+
+#[derive(Debug)]
+struct Scanner {
+    // 1-based line of the scanner's header.
+    line: usize,
+    beacons: Vec<Point>,
+    // How often each fingerprint of the vectors between two beacons occurs.
+    fingerprints: HashMap<[i32; 3], usize>,
+}
+
+impl Scanner {
+    fn new(line: usize, beacons: Vec<Point>) -> Self {
+        let mut fingerprints = HashMap::new();
+        beacons
+            .iter()
+            .tuple_combinations()
+            .for_each(|(a, b)| *fingerprints.entry((*a - *b).fingerprint()).or_insert(0) += 1);
+        Self {
+            line,
+            beacons,
+            fingerprints,
+        }
+    }
+
+    // Whether the two scanners might share `OVERLAP` beacons, which share a fingerprint for
+    // each pair of them.
+    fn may_overlap(&self, other: &Scanner) -> bool {
+        self.fingerprints
+            .iter()
+            .map(|(f, n)| other.fingerprints.get(f).map_or(0, |m| *n.min(m)))
+            .sum::<usize>()
+            >= OVERLAP * (OVERLAP - 1) / 2
+    }
+
+    // Indices of the beacons at either end of a vector whose fingerprint `other` has as well,
+    // only those can be seen by both.
+    fn shared(&self, other: &Scanner) -> Vec<usize> {
+        let mut ends = vec![false; self.beacons.len()];
+        self.beacons
+            .iter()
+            .enumerate()
+            .tuple_combinations()
+            .filter(|((_, a), (_, b))| other.fingerprints.contains_key(&(**a - **b).fingerprint()))
+            .for_each(|((i, _), (j, _))| {
+                ends[i] = true;
+                ends[j] = true;
+            });
+        (0..ends.len()).filter(|i| ends[*i]).collect()
+    }
+
+    // The position of this scanner and its beacons in the coordinates `known` has the beacons
+    // of `other` in, if enough of them line up in one of the rotations.
+    fn align(&self, other: &Scanner, known: &[Point]) -> Option<(Point, Vec<Point>)> {
+        let known = other
+            .shared(self)
+            .into_iter()
+            .map(|i| known[i])
+            .collect::<Vec<Point>>();
+        let ours = self.shared(other);
+        Rotation::ALL.into_iter().find_map(|r| {
+            let mut offsets: HashMap<Vector, usize> = HashMap::new();
+            known.iter().cartesian_product(&ours).for_each(|(a, i)| {
+                *offsets.entry(*a - self.beacons[*i].rotate(r)).or_insert(0) += 1
+            });
+            let (offset, _) = offsets.into_iter().find(|(_, n)| *n >= OVERLAP)?;
+            let rotated = self.beacons.iter().map(|b| b.rotate(r));
+            Some((
+                Point::ORIGIN + offset,
+                rotated.map(|b| b + offset).collect(),
+            ))
+        })
+    }
+}
+
+// All beacons and scanners, in the coordinates of scanner 0.
+#[derive(Debug)]
+pub struct Map {
+    beacons: HashSet<Point>,
+    scanners: Vec<Point>,
+}
+
+// The beacons each scanner sees, relative to itself.
+fn scanners(s: &str) -> Result<Vec<Scanner>, AocError> {
+    let scanners = input::paragraphs(s)
+        .into_iter()
+        .enumerate()
+        .map(|(n, (line, p))| {
+            let (header, beacons) = p.split_once('\n').unwrap_or((p, ""));
+            if header != format!("--- scanner {n} ---") {
+                return Err(
+                    AocError::new(1, format!("expected '--- scanner {n} ---'")).on_line(line)
+                );
+            }
+            let beacons = error::parse_lines(beacons).map_err(|e| e.on_line(line + 1))?;
+            Ok(Scanner::new(line, beacons))
+        })
+        .collect::<Result<Vec<Scanner>, AocError>>()?;
+    if scanners.is_empty() {
+        return Err(AocError::new(1, "expected '--- scanner 0 ---'"));
+    }
+    Ok(scanners)
+}
+
+// Both parts need the scanners located, so that is part of reading the report. Scanners that
+// cannot be placed make it malformed.
+impl FromStr for Map {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, AocError> {
+        locate(&scanners(s)?)
+    }
+}
+
+// Places the scanners one by one next to those already placed, starting from scanner 0.
+fn locate(scanners: &[Scanner]) -> Result<Map, AocError> {
+    let first = &scanners[0];
+    let mut placed: Vec<Option<(Point, Vec<Point>)>> = scanners.iter().map(|_| None).collect();
+    placed[0] = Some((Point::ORIGIN, first.beacons.clone()));
+    let mut todo = vec![0];
+    while let Some(i) = todo.pop() {
+        for j in 0..scanners.len() {
+            cancel::check();
+            if placed[j].is_some() || !scanners[i].may_overlap(&scanners[j]) {
+                continue;
+            }
+            let known = &placed[i].as_ref().unwrap().1;
+            if let Some(located) = scanners[j].align(&scanners[i], known) {
+                placed[j] = Some(located);
+                todo.push(j);
+            }
+        }
+    }
+    if let Some(lost) = placed.iter().position(Option::is_none) {
+        return Err(AocError::new(
+            1,
+            format!("scanner {lost} shares {OVERLAP} beacons with no other scanner"),
+        )
+        .on_line(scanners[lost].line));
+    }
+    let (scanners, beacons): (Vec<Point>, Vec<Vec<Point>>) = placed.into_iter().flatten().unzip();
+    Ok(Map {
+        beacons: beacons.into_iter().flatten().collect(),
+        scanners,
+    })
+}
+
+pub fn solution(m: &Map) -> usize {
+    m.beacons.len()
+}
+
+pub fn solution2(m: &Map) -> i32 {
+    m.scanners
+        .iter()
+        .tuple_combinations()
+        .map(|(a, b)| a.manhattan(*b))
+        .max()
+        .unwrap_or(0)
+}
+
+#[test]
+fn test_run() {
+    let input = fs::read_to_string("src/inputs/aoc_19_sample.input").unwrap();
+    let m = Day19::parse(&input).unwrap();
+    assert_eq!(
+        m.scanners,
+        [
+            Point::new(0, 0, 0),
+            Point::new(68, -1246, -43),
+            Point::new(1105, -1205, 1229),
+            Point::new(-92, -2380, -20),
+            Point::new(-20, -1133, 1061)
+        ]
+    );
+    assert_eq!(solution(&m), 79);
+    assert_eq!(solution2(&m), 3621);
+    // A generated input, its answers only guard against regressions.
+    let input = fs::read_to_string("src/inputs/aoc_19.input").unwrap();
+    let m = Day19::parse(&input).unwrap();
+    assert_eq!(solution(&m), 672);
+    assert_eq!(solution2(&m), 6388);
+
+    let sample = fs::read_to_string("src/inputs/aoc_19_sample.input").unwrap();
+    let paragraphs = input::paragraphs(&sample);
+    // Without scanner 1 none of the others overlaps scanner 0.
+    let without_1 = [0, 2, 3, 4]
+        .iter()
+        .enumerate()
+        .map(|(n, i)| {
+            let beacons = paragraphs[*i].1.split_once('\n').unwrap().1;
+            format!("--- scanner {n} ---\n{beacons}")
+        })
+        .join("\n\n");
+    assert_eq!(
+        Day19::parse(&without_1).unwrap_err(),
+        AocError::new(1, "scanner 1 shares 12 beacons with no other scanner").on_line(28)
+    );
+    assert_eq!(
+        Day19::parse("--- scanner 1 ---\n1,2,3").unwrap_err(),
+        AocError::new(1, "expected '--- scanner 0 ---'")
+    );
+    assert_eq!(
+        Day19::parse("--- scanner 0 ---\n1,2,3\n1,2").unwrap_err(),
+        AocError::new(1, "expected 'x,y,z', found '1,2'").on_line(3)
+    );
+}
+
+pub struct Day19;
+
+impl Solver for Day19 {
+    type Input<'a> = Map;
+    type Output1 = usize;
+    type Output2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        input.parse()
+    }
+
+    fn part1(m: &Self::Input<'_>) -> usize {
+        solution(m)
+    }
+
+    fn part2(m: &Self::Input<'_>) -> i32 {
+        solution2(m)
+    }
+}
@@ -1,21 +1,31 @@
+#[cfg(test)]
 use std::fs;
-use std::ops::Add;
+use std::{ops::Add, str::FromStr};
+
+use crate::{
+    error::{self, AocError},
+    solver::Solver,
+};
 
 #[derive(Debug)]
-enum Move {
+pub enum Move {
     Forward(i32),
     Up(i32),
     Down(i32),
-    Invalid,
 }
 
-impl From<&str> for Move {
-    fn from(s: &str) -> Self {
-        match s.split_once(' ') {
-            Some(("forward", v)) => Move::Forward(v.parse::<i32>().unwrap()),
-            Some(("down", v)) => Move::Down(v.parse::<i32>().unwrap()),
-            Some(("up", v)) => Move::Up(v.parse::<i32>().unwrap()),
-            _ => Move::Invalid,
+impl FromStr for Move {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, AocError> {
+        let (direction, v) = s.split_once(' ').ok_or_else(|| {
+            AocError::new(1, format!("expected '<direction> <amount>', found '{s}'"))
+        })?;
+        match direction {
+            "forward" => Ok(Move::Forward(error::number(s, v)?)),
+            "down" => Ok(Move::Down(error::number(s, v)?)),
+            "up" => Ok(Move::Up(error::number(s, v)?)),
+            _ => Err(AocError::new(1, format!("unknown direction '{direction}'"))),
         }
     }
 }
@@ -37,15 +47,13 @@ impl Add for Position {
     }
 }
 
-fn solution(input: &str) -> i32 {
-    let x: Position = input
-        .lines()
-        .map(Move::from)
-        .map(|m| match m {
+pub fn solution(moves: &[Move]) -> i32 {
+    let x: Position = moves
+        .iter()
+        .map(|m| match *m {
             Move::Forward(v) => Position { h: v, d: 0 },
             Move::Down(v) => Position { h: 0, d: v },
             Move::Up(v) => Position { h: 0, d: -v },
-            Move::Invalid => Position { h: 0, d: 0 },
         })
         .fold(Position { h: 0, d: 0 }, |a, b| a + b);
     x.d * x.h
@@ -82,31 +90,51 @@ impl Position2 {
     }
 }
 
-fn solution2(input: &str) -> i32 {
-    let x: Position2 =
-        input
-            .lines()
-            .map(Move::from)
-            .fold(Position2 { h: 0, d: 0, aim: 0 }, |a, b| match b {
-                Move::Forward(v) => a.forward(v),
-                Move::Down(v) => a.down(v),
-                Move::Up(v) => a.up(v),
-                Move::Invalid => a,
-            });
+pub fn solution2(moves: &[Move]) -> i32 {
+    let x: Position2 = moves
+        .iter()
+        .fold(Position2 { h: 0, d: 0, aim: 0 }, |a, b| match *b {
+            Move::Forward(v) => a.forward(v),
+            Move::Down(v) => a.down(v),
+            Move::Up(v) => a.up(v),
+        });
     x.d * x.h
 }
 
 #[test]
 fn test_run() {
     let input = fs::read_to_string("src/inputs/aoc_2_sample.input").unwrap();
-    assert_eq!(solution(&input), 150);
-    assert_eq!(solution2(&input), 900);
+    let moves = Day2::parse(&input).unwrap();
+    assert_eq!(solution(&moves), 150);
+    assert_eq!(solution2(&moves), 900);
     let input = fs::read_to_string("src/inputs/aoc_2.input").unwrap();
-    assert_eq!(solution(&input), 1813801);
-    assert_eq!(solution2(&input), 1960569556);
+    let moves = Day2::parse(&input).unwrap();
+    assert_eq!(solution(&moves), 1813801);
+    assert_eq!(solution2(&moves), 1960569556);
+    assert_eq!(
+        Day2::parse("up 3\nforward five").unwrap_err(),
+        AocError::new(9, "expected a number, found 'five'").on_line(2)
+    );
+    assert!(Day2::parse("backward 2").is_err());
+    assert!(Day2::parse("forward").is_err());
 }
 
-pub fn run() -> (String, String) {
-    let input = fs::read_to_string("src/inputs/aoc_2.input").unwrap();
-    (solution(&input).to_string(), solution2(&input).to_string())
+pub struct Day2;
+
+impl Solver for Day2 {
+    type Input<'a> = Vec<Move>;
+    type Output1 = i32;
+    type Output2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        error::parse_lines(input)
+    }
+
+    fn part1(moves: &Self::Input<'_>) -> i32 {
+        solution(moves)
+    }
+
+    fn part2(moves: &Self::Input<'_>) -> i32 {
+        solution2(moves)
+    }
 }
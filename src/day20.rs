@@ -0,0 +1,206 @@
+#[cfg(test)]
+use std::fs;
+use std::str::FromStr;
+
+use crate::{error::AocError, grid::Grid, input, solver::Solver};
+
+fn pixel(c: char, column: usize) -> Result<bool, AocError> {
+    match c {
+        '#' => Ok(true),
+        '.' => Ok(false),
+        c => Err(AocError::new(
+            column,
+            format!("expected '#' or '.', found '{}'", c.escape_default()),
+        )),
+    }
+}
+
+// An endless image, all pixels outside of `pixels` are lit if `background` is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    pixels: Grid<bool>,
+    background: bool,
+}
+
+impl Image {
+    fn get(&self, (x, y): (i64, i64)) -> bool {
+        match (usize::try_from(x), usize::try_from(y)) {
+            (Ok(x), Ok(y)) => *self.pixels.get((x, y)).unwrap_or(&self.background),
+            _ => self.background,
+        }
+    }
+
+    // Each step the image grows by a pixel on every side, the background beyond it changes
+    // all at once.
+    fn enhance(&self, algorithm: &[bool]) -> Image {
+        let pixels = Grid::from_fn(
+            self.pixels.width() + 2,
+            self.pixels.height() + 2,
+            |(x, y)| {
+                let index = (0..9).fold(0, |index, i| {
+                    let p = (x as i64 + i % 3 - 2, y as i64 + i / 3 - 2);
+                    index << 1 | self.get(p) as usize
+                });
+                algorithm[index]
+            },
+        );
+        Image {
+            pixels,
+            background: algorithm[if self.background { 511 } else { 0 }],
+        }
+    }
+
+    // None if the background is lit, which makes endlessly many lit pixels.
+    pub fn lit(&self) -> Option<usize> {
+        (!self.background).then(|| self.pixels.iter().filter(|(_, p)| **p).count())
+    }
+
+    // The part of the image that is not background, plus the pixels the background has
+    // grown over since.
+    pub fn render(&self) -> String {
+        self.pixels.render(|p| if *p { '#' } else { '.' })
+    }
+}
+
+#[derive(Debug)]
+pub struct Trench {
+    algorithm: Vec<bool>,
+    image: Image,
+}
+
+impl FromStr for Trench {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, AocError> {
+        let paragraphs = input::paragraphs(s);
+        let first = paragraphs.first().map_or("", |(_, p)| p);
+        if first.contains('\n') {
+            return Err(AocError::new(1, "expected a blank line").on_line(2));
+        }
+        let algorithm = first
+            .chars()
+            .enumerate()
+            .map(|(i, c)| pixel(c, i + 1))
+            .collect::<Result<Vec<bool>, AocError>>()?;
+        if algorithm.len() != 512 {
+            return Err(AocError::new(
+                algorithm.len().min(512) + 1,
+                format!("expected 512 pixels, found {}", algorithm.len()),
+            ));
+        }
+        // A lit background would stay lit after every step.
+        if algorithm[0] && algorithm[511] {
+            return Err(AocError::new(
+                512,
+                "expected '.' as the first or last pixel, endlessly many pixels stay lit",
+            ));
+        }
+        let (line, image) = match &paragraphs[..] {
+            [_, image] => *image,
+            [_, _, (line, _), ..] => {
+                return Err(AocError::new(1, "expected a single image").on_line(*line))
+            }
+            _ => return Err(AocError::new(1, "expected an image").on_line(3)),
+        };
+        Ok(Self {
+            algorithm,
+            image: Image {
+                pixels: Grid::parse(image, pixel).map_err(|e| e.on_line(line))?,
+                background: false,
+            },
+        })
+    }
+}
+
+impl Trench {
+    // The image after `steps` enhancements.
+    pub fn enhanced(&self, steps: usize) -> Image {
+        (0..steps).fold(self.image.clone(), |image, _| {
+            image.enhance(&self.algorithm)
+        })
+    }
+}
+
+// After an even number of steps the background is dark again.
+pub fn solution(t: &Trench) -> usize {
+    t.enhanced(2).lit().unwrap()
+}
+
+pub fn solution2(t: &Trench) -> usize {
+    t.enhanced(50).lit().unwrap()
+}
+
+#[test]
+fn test_run() {
+    let input = fs::read_to_string("src/inputs/aoc_20_sample.input").unwrap();
+    let t = Day20::parse(&input).unwrap();
+    assert_eq!(solution(&t), 35);
+    assert_eq!(solution2(&t), 3351);
+    assert_eq!(t.enhanced(0).render(), input.split_once("\n\n").unwrap().1);
+    // A generated input, its answers only guard against regressions.
+    let input = fs::read_to_string("src/inputs/aoc_20.input").unwrap();
+    let t = Day20::parse(&input).unwrap();
+    assert_eq!(solution(&t), 5636);
+    assert_eq!(solution2(&t), 19744);
+
+    // Lit if the middle pixel is, the image stays the same and gets a dark border.
+    let same = (0..512)
+        .map(|i| if i & 16 != 0 { '#' } else { '.' })
+        .collect::<String>();
+    let t = Day20::parse(&format!("{same}\n\n#.\n.#")).unwrap();
+    assert_eq!(t.enhanced(1).render(), "....\n.#..\n..#.\n....");
+    // Lit if the middle pixel is not, every step flips the whole image.
+    let flip = same.replace('#', "x").replace('.', "#").replace('x', ".");
+    let t = Day20::parse(&format!("{flip}\n\n#.\n.#")).unwrap();
+    let once = t.enhanced(1);
+    assert_eq!(
+        (once.render().as_str(), once.lit()),
+        ("####\n#.##\n##.#\n####", None)
+    );
+    assert_eq!(t.enhanced(2).lit(), Some(2));
+
+    let all = "#".repeat(512);
+    assert_eq!(
+        Day20::parse(&format!("{all}\n\n#")).unwrap_err(),
+        AocError::new(
+            512,
+            "expected '.' as the first or last pixel, endlessly many pixels stay lit"
+        )
+    );
+    assert_eq!(
+        Day20::parse(&format!("{}\n\n#", &same[1..])).unwrap_err(),
+        AocError::new(512, "expected 512 pixels, found 511")
+    );
+    assert_eq!(
+        Day20::parse(&format!("{same}\n\n#.\n.x")).unwrap_err(),
+        AocError::new(2, "expected '#' or '.', found 'x'").on_line(4)
+    );
+    assert_eq!(
+        Day20::parse(&same).unwrap_err(),
+        AocError::new(1, "expected an image").on_line(3)
+    );
+    assert_eq!(
+        Day20::parse(&format!("{same}\n#\n\n#")).unwrap_err(),
+        AocError::new(1, "expected a blank line").on_line(2)
+    );
+}
+
+pub struct Day20;
+
+impl Solver for Day20 {
+    type Input<'a> = Trench;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        input.parse()
+    }
+
+    fn part1(t: &Self::Input<'_>) -> usize {
+        solution(t)
+    }
+
+    fn part2(t: &Self::Input<'_>) -> usize {
+        solution2(t)
+    }
+}
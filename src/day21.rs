@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+#[cfg(test)]
+use std::fs;
+use std::str::FromStr;
+
+use crate::{
+    error::{self, AocError},
+    solver::Solver,
+};
+
+// How a game is played, the puzzle uses `PRACTICE` and `DIRAC` on the same board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rules {
+    // Spaces on the circular board, numbered from 1.
+    pub board: u32,
+    // The score that wins.
+    pub target: u32,
+    pub faces: u32,
+    pub rolls_per_turn: u32,
+}
+
+impl Rules {
+    pub const PRACTICE: Rules = Rules {
+        board: 10,
+        target: 1000,
+        faces: 100,
+        rolls_per_turn: 3,
+    };
+
+    pub const DIRAC: Rules = Rules {
+        board: 10,
+        target: 21,
+        faces: 3,
+        rolls_per_turn: 3,
+    };
+
+    fn advance(&self, position: u32, steps: u32) -> u32 {
+        (position - 1 + steps) % self.board + 1
+    }
+}
+
+// Rolls 1, 2 and so on up to the number of faces, then starts over at 1.
+#[derive(Debug)]
+struct DeterministicDie {
+    faces: u32,
+    rolled: u64,
+}
+
+impl Iterator for DeterministicDie {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        self.rolled += 1;
+        Some(((self.rolled - 1) % self.faces as u64) as u32 + 1)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct State {
+    positions: [u32; 2],
+    scores: [u32; 2],
+    // The player about to roll.
+    turn: usize,
+}
+
+// Counts the universes each player wins in, memoized for a single set of rules.
+struct Universes {
+    rules: Rules,
+    // The number of ways to roll each total in a turn.
+    totals: Vec<(u32, u128)>,
+    wins: HashMap<State, [u128; 2]>,
+}
+
+impl Universes {
+    fn new(rules: Rules) -> Self {
+        let mut ways = vec![1u128];
+        (0..rules.rolls_per_turn).for_each(|_| {
+            let mut next = vec![0; ways.len() + rules.faces as usize];
+            ways.iter().enumerate().for_each(|(total, n)| {
+                (1..=rules.faces as usize).for_each(|face| next[total + face] += n)
+            });
+            ways = next;
+        });
+        Self {
+            rules,
+            totals: (0..ways.len() as u32)
+                .zip(ways)
+                .filter(|(_, n)| *n != 0)
+                .collect(),
+            wins: HashMap::new(),
+        }
+    }
+
+    fn count(&mut self, state: State) -> [u128; 2] {
+        if let Some(wins) = self.wins.get(&state) {
+            return *wins;
+        }
+        let mut wins = [0, 0];
+        for i in 0..self.totals.len() {
+            let (total, ways) = self.totals[i];
+            let mut next = state;
+            let player = state.turn;
+            next.positions[player] = self.rules.advance(state.positions[player], total);
+            next.scores[player] += next.positions[player];
+            if next.scores[player] >= self.rules.target {
+                wins[player] += ways;
+                continue;
+            }
+            next.turn = 1 - player;
+            let [a, b] = self.count(next);
+            wins = [wins[0] + ways * a, wins[1] + ways * b];
+        }
+        self.wins.insert(state, wins);
+        wins
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Game {
+    start: [u32; 2],
+}
+
+impl FromStr for Game {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, AocError> {
+        let players = error::lines(s, |l| {
+            let (n, p) = l
+                .strip_prefix("Player ")
+                .and_then(|r| r.split_once(" starting position: "))
+                .ok_or_else(|| {
+                    AocError::new(
+                        1,
+                        format!("expected 'Player <n> starting position: <space>', found '{l}'"),
+                    )
+                })?;
+            match error::number(l, p)? {
+                space @ 1..=10 => Ok((error::number::<u32>(l, n)?, space)),
+                _ => Err(AocError::at(l, p, "expected a space from 1 to 10")),
+            }
+        })?;
+        match players[..] {
+            [(1, a), (2, b)] => Ok(Game { start: [a, b] }),
+            _ => Err(AocError::new(1, "expected players 1 and 2, one per line")),
+        }
+    }
+}
+
+impl Game {
+    // The losing score times the number of rolls, once a player wins.
+    pub fn practice(&self, rules: Rules) -> u64 {
+        let mut die = DeterministicDie {
+            faces: rules.faces,
+            rolled: 0,
+        };
+        let (mut positions, mut scores) = (self.start, [0, 0]);
+        let mut player = 0;
+        loop {
+            let steps = die.by_ref().take(rules.rolls_per_turn as usize).sum();
+            positions[player] = rules.advance(positions[player], steps);
+            scores[player] += positions[player];
+            if scores[player] >= rules.target {
+                return scores[1 - player] as u64 * die.rolled;
+            }
+            player = 1 - player;
+        }
+    }
+
+    // The number of universes each player wins in.
+    pub fn dirac(&self, rules: Rules) -> [u128; 2] {
+        Universes::new(rules).count(State {
+            positions: self.start,
+            scores: [0, 0],
+            turn: 0,
+        })
+    }
+}
+
+pub fn solution(g: &Game) -> u64 {
+    g.practice(Rules::PRACTICE)
+}
+
+pub fn solution2(g: &Game) -> u128 {
+    let [a, b] = g.dirac(Rules::DIRAC);
+    a.max(b)
+}
+
+#[test]
+fn test_run() {
+    let input = fs::read_to_string("src/inputs/aoc_21_sample.input").unwrap();
+    let g = Day21::parse(&input).unwrap();
+    assert_eq!(solution(&g), 739785);
+    assert_eq!(solution2(&g), 444356092776315);
+    assert_eq!(g.dirac(Rules::DIRAC), [444356092776315, 341960390180808]);
+    // A generated input, its answers only guard against regressions.
+    let input = fs::read_to_string("src/inputs/aoc_21.input").unwrap();
+    let g = Day21::parse(&input).unwrap();
+    assert_eq!(solution(&g), 428736);
+    assert_eq!(solution2(&g), 57328067654557);
+
+    // Player 1 wins on the first turn in every universe.
+    let rules = Rules {
+        target: 1,
+        ..Rules::DIRAC
+    };
+    assert_eq!(g.dirac(rules), [27, 0]);
+    // A single universe, the same game as with a deterministic die.
+    let rules = Rules {
+        board: 7,
+        target: 30,
+        faces: 1,
+        rolls_per_turn: 2,
+    };
+    let [a, b] = g.dirac(rules);
+    assert_eq!(a + b, 1);
+    // Players move 3 spaces a turn. Player 1 scores 7, 17 and 20, player 2 scores 1 and 5
+    // meanwhile, 5 turns of 3 rolls.
+    assert_eq!(
+        Game { start: [4, 8] }.practice(Rules {
+            target: 20,
+            faces: 1,
+            ..Rules::PRACTICE
+        }),
+        5 * 15
+    );
+
+    assert_eq!(
+        Day21::parse("Player 1 starting position: 11\nPlayer 2 starting position: 8").unwrap_err(),
+        AocError::new(29, "expected a space from 1 to 10")
+    );
+    assert_eq!(
+        Day21::parse("Player 2 starting position: 4\nPlayer 1 starting position: 8").unwrap_err(),
+        AocError::new(1, "expected players 1 and 2, one per line")
+    );
+    assert_eq!(
+        Day21::parse("Player 1 starting position: 4\nPlayer 2 starts at 8").unwrap_err(),
+        AocError::new(
+            1,
+            "expected 'Player <n> starting position: <space>', found 'Player 2 starts at 8'"
+        )
+        .on_line(2)
+    );
+}
+
+pub struct Day21;
+
+impl Solver for Day21 {
+    type Input<'a> = Game;
+    type Output1 = u64;
+    type Output2 = u128;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        input.parse()
+    }
+
+    fn part1(g: &Self::Input<'_>) -> u64 {
+        solution(g)
+    }
+
+    fn part2(g: &Self::Input<'_>) -> u128 {
+        solution2(g)
+    }
+}
@@ -0,0 +1,146 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::HashMap;
+#[cfg(test)]
+use std::fs;
+use std::str::FromStr;
+
+use crate::{
+    error::{self, AocError},
+    geometry::{Cuboid, Point},
+    solver::Solver,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Step {
+    on: bool,
+    cuboid: Cuboid,
+}
+
+impl FromStr for Step {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, AocError> {
+        lazy_static! {
+            static ref RE: Regex = Regex::new(
+                r"^(on|off) x=(-?\d+)\.\.(-?\d+),y=(-?\d+)\.\.(-?\d+),z=(-?\d+)\.\.(-?\d+)$"
+            )
+            .unwrap();
+        }
+        let caps = RE.captures(s).ok_or_else(|| {
+            AocError::new(
+                1,
+                "expected 'on|off x=<min>..<max>,y=<min>..<max>,z=<min>..<max>'",
+            )
+        })?;
+        let coord = |i: usize| error::number(s, &caps[i]);
+        let cuboid = Cuboid::new(
+            Point::new(coord(2)?, coord(4)?, coord(6)?),
+            Point::new(coord(3)?, coord(5)?, coord(7)?),
+        );
+        let (min, max) = (cuboid.min, cuboid.max);
+        let ranges = [(min.x, max.x), (min.y, max.y), (min.z, max.z)];
+        if let Some(axis) = ranges.iter().position(|(a, b)| a > b) {
+            return Err(AocError::at(
+                s,
+                caps.get(2 + 2 * axis).unwrap().as_str(),
+                "expected a range from low to high",
+            ));
+        }
+        Ok(Self {
+            on: &caps[1] == "on",
+            cuboid,
+        })
+    }
+}
+
+// The number of cubes lit after the steps that fall inside `region`, or all of them.
+//
+// Lit cubes are kept as cuboids with a count. A cuboid lit twice over gets counted twice, so
+// every step first cancels its overlap with what is counted so far and then, if it turns
+// cubes on, counts itself once.
+fn reboot(steps: &[Step], region: Option<Cuboid>) -> i64 {
+    let mut counts: HashMap<Cuboid, i64> = HashMap::new();
+    for step in steps {
+        let Some(cuboid) = region.map_or(Some(step.cuboid), |r| r.intersection(&step.cuboid))
+        else {
+            continue;
+        };
+        let mut update: HashMap<Cuboid, i64> = HashMap::new();
+        counts.iter().for_each(|(c, n)| {
+            if let Some(overlap) = c.intersection(&cuboid) {
+                *update.entry(overlap).or_insert(0) -= n;
+            }
+        });
+        if step.on {
+            *update.entry(cuboid).or_insert(0) += 1;
+        }
+        update
+            .into_iter()
+            .for_each(|(c, n)| *counts.entry(c).or_insert(0) += n);
+        counts.retain(|_, n| *n != 0);
+    }
+    counts.iter().map(|(c, n)| c.volume() * n).sum()
+}
+
+pub fn solution(steps: &[Step]) -> i64 {
+    let region = Cuboid::new(Point::new(-50, -50, -50), Point::new(50, 50, 50));
+    reboot(steps, Some(region))
+}
+
+pub fn solution2(steps: &[Step]) -> i64 {
+    reboot(steps, None)
+}
+
+#[test]
+fn test_run() {
+    let input = fs::read_to_string("src/inputs/aoc_22_sample.input").unwrap();
+    let steps = Day22::parse(&input).unwrap();
+    assert_eq!(solution(&steps), 39);
+    assert_eq!(solution2(&steps), 39);
+    // A generated input, its answers only guard against regressions.
+    let input = fs::read_to_string("src/inputs/aoc_22.input").unwrap();
+    let steps = Day22::parse(&input).unwrap();
+    assert_eq!(solution(&steps), 176875);
+    assert_eq!(solution2(&steps), 1348123854202352);
+
+    // Only the part inside the region counts for the initialization.
+    let steps = Day22::parse("on x=40..60,y=0..0,z=0..0\noff x=45..45,y=0..0,z=0..0").unwrap();
+    assert_eq!((solution(&steps), solution2(&steps)), (10, 20));
+    assert_eq!(
+        Day22::parse("on x=1..2,y=5..3,z=0..0").unwrap_err(),
+        AocError::new(13, "expected a range from low to high")
+    );
+    assert_eq!(
+        Day22::parse("on x=1..2,y=3..5,z=0..0\ntoggle x=1..2,y=3..5,z=0..0").unwrap_err(),
+        AocError::new(
+            1,
+            "expected 'on|off x=<min>..<max>,y=<min>..<max>,z=<min>..<max>'"
+        )
+        .on_line(2)
+    );
+    assert_eq!(
+        Day22::parse("off x=1..2,y=3..5,z=0..99999999999").unwrap_err(),
+        AocError::new(24, "expected a number, found '99999999999'")
+    );
+}
+
+pub struct Day22;
+
+impl Solver for Day22 {
+    type Input<'a> = Vec<Step>;
+    type Output1 = i64;
+    type Output2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        error::parse_lines(input)
+    }
+
+    fn part1(steps: &Self::Input<'_>) -> i64 {
+        solution(steps)
+    }
+
+    fn part2(steps: &Self::Input<'_>) -> i64 {
+        solution2(steps)
+    }
+}
@@ -0,0 +1,251 @@
+use itertools::{EitherOrBoth, Itertools};
+#[cfg(test)]
+use std::fs;
+use std::str::FromStr;
+
+use crate::{error::AocError, search, solver::Solver};
+
+const EMPTY: u8 = b'.';
+const HALLWAY: usize = 11;
+// The two lines the notes unfold between the first and the last line of the rooms.
+const FOLDED: [[u8; 4]; 2] = [*b"DCBA", *b"DBAC"];
+
+// The hallway space right outside a room, amphipods never stop there.
+fn door(room: usize) -> usize {
+    2 + 2 * room
+}
+
+// Amphipods are 'A' to 'D', their rooms are 0 to 3.
+fn home(amphipod: u8) -> usize {
+    (amphipod - b'A') as usize
+}
+
+fn energy(amphipod: u8) -> usize {
+    10usize.pow(home(amphipod) as u32)
+}
+
+// The spaces of a burrow with rooms `DEPTH` amphipods deep, holding 'A' to 'D' or `EMPTY`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Burrow<const DEPTH: usize> {
+    hallway: [u8; HALLWAY],
+    // From the hallway down.
+    rooms: [[u8; DEPTH]; 4],
+}
+
+impl<const DEPTH: usize> FromStr for Burrow<DEPTH> {
+    type Err = AocError;
+
+    // The diagram from the notes, with an empty hallway. A '?' stands for an amphipod.
+    fn from_str(s: &str) -> Result<Self, AocError> {
+        let diagram = ["#############", "#...........#"]
+            .into_iter()
+            .chain((0..DEPTH).map(|d| {
+                if d == 0 {
+                    "###?#?#?#?###"
+                } else {
+                    "  #?#?#?#?#"
+                }
+            }))
+            .chain(["  #########"])
+            .collect::<Vec<&str>>();
+        let lines = s.lines().collect::<Vec<&str>>();
+        if lines.len() != diagram.len() {
+            return Err(
+                AocError::new(1, format!("expected {} lines", diagram.len()))
+                    .on_line(lines.len().min(diagram.len()) + 1),
+            );
+        }
+        let mut burrow = Burrow {
+            hallway: [EMPTY; HALLWAY],
+            rooms: [[EMPTY; DEPTH]; 4],
+        };
+        for (i, (line, expected)) in lines.iter().zip(&diagram).enumerate() {
+            let line = line.trim_end();
+            for (column, pair) in line.bytes().zip_longest(expected.bytes()).enumerate() {
+                let error = match pair {
+                    EitherOrBoth::Both(a @ b'A'..=b'D', b'?') => {
+                        burrow.rooms[column / 2 - 1][i - 2] = a;
+                        continue;
+                    }
+                    EitherOrBoth::Both(a, b'?') => format!(
+                        "expected an amphipod from 'A' to 'D', found '{}'",
+                        (a as char).escape_default()
+                    ),
+                    EitherOrBoth::Both(a, b) if a == b => continue,
+                    EitherOrBoth::Both(_, b) | EitherOrBoth::Right(b) => {
+                        format!("expected '{}'", b as char)
+                    }
+                    EitherOrBoth::Left(_) => "expected the end of the line".to_string(),
+                };
+                return Err(AocError::new(column + 1, error).on_line(i + 1));
+            }
+        }
+        let mut counts = [0; 4];
+        burrow
+            .rooms
+            .iter()
+            .flatten()
+            .for_each(|a| counts[home(*a)] += 1);
+        if counts.iter().any(|n| *n != DEPTH) {
+            return Err(
+                AocError::new(1, format!("expected {DEPTH} amphipods of each type")).on_line(3),
+            );
+        }
+        Ok(burrow)
+    }
+}
+
+impl<const DEPTH: usize> Burrow<DEPTH> {
+    fn organized(&self) -> bool {
+        (0..4).all(|r| self.rooms[r].iter().all(|a| *a == b'A' + r as u8))
+    }
+
+    // Whether the hallway is empty from next to `from` up to and including `to`.
+    fn clear(&self, from: usize, to: usize) -> bool {
+        let path = if from < to {
+            from + 1..to + 1
+        } else {
+            to..from
+        };
+        self.hallway[path].iter().all(|s| *s == EMPTY)
+    }
+
+    // The burrows a single amphipod can move to and the energy it takes.
+    fn moves(&self) -> Vec<(Self, usize)> {
+        // Moving an amphipod from the hallway into its room never hurts, so when one can, that
+        // is the only move worth considering.
+        for (x, a) in self.hallway.iter().enumerate() {
+            let (a, room) = match *a {
+                EMPTY => continue,
+                a => (a, home(a)),
+            };
+            let spaces = &self.rooms[room];
+            if !spaces.iter().all(|s| *s == EMPTY || *s == a) || !self.clear(x, door(room)) {
+                continue;
+            }
+            let depth = spaces.iter().rposition(|s| *s == EMPTY).unwrap();
+            let mut next = *self;
+            next.hallway[x] = EMPTY;
+            next.rooms[room][depth] = a;
+            return vec![(next, (x.abs_diff(door(room)) + depth + 1) * energy(a))];
+        }
+        let mut moves = vec![];
+        for room in 0..4 {
+            let spaces = &self.rooms[room];
+            let Some(depth) = spaces.iter().position(|s| *s != EMPTY) else {
+                continue;
+            };
+            // Amphipods in their own room with none below them that need to get out.
+            if spaces[depth..].iter().all(|a| home(*a) == room) {
+                continue;
+            }
+            let a = spaces[depth];
+            for x in (0..HALLWAY).filter(|x| ![2, 4, 6, 8].contains(x)) {
+                if !self.clear(door(room), x) {
+                    continue;
+                }
+                let mut next = *self;
+                next.rooms[room][depth] = EMPTY;
+                next.hallway[x] = a;
+                moves.push((next, (depth + 1 + x.abs_diff(door(room))) * energy(a)));
+            }
+        }
+        moves
+    }
+
+    // The least energy it takes to get every amphipod into its room.
+    pub fn organize(&self) -> Option<usize> {
+        search::dijkstra(*self, Self::organized, Self::moves)
+    }
+}
+
+impl Burrow<2> {
+    // The full diagram, with the lines the notes folded away.
+    pub fn unfold(&self) -> Burrow<4> {
+        Burrow {
+            hallway: self.hallway,
+            rooms: [0, 1, 2, 3].map(|r| {
+                let [top, bottom] = self.rooms[r];
+                [top, FOLDED[0][r], FOLDED[1][r], bottom]
+            }),
+        }
+    }
+}
+
+// Not every burrow can be organized once unfolded, the notes of real inputs always can.
+pub fn solution(b: &Burrow<2>) -> usize {
+    b.organize().unwrap()
+}
+
+pub fn solution2(b: &Burrow<2>) -> usize {
+    b.unfold().organize().unwrap()
+}
+
+#[test]
+fn test_run() {
+    let input = fs::read_to_string("src/inputs/aoc_23_sample.input").unwrap();
+    let b = Day23::parse(&input).unwrap();
+    assert_eq!(solution(&b), 12521);
+    assert_eq!(solution2(&b), 44169);
+    let mut unfolded = input.lines().collect::<Vec<&str>>();
+    unfolded.splice(3..3, ["  #D#C#B#A#", "  #D#B#A#C#"]);
+    assert_eq!(unfolded.join("\n").parse(), Ok(b.unfold()));
+    // A generated input, its answers only guard against regressions.
+    let input = fs::read_to_string("src/inputs/aoc_23.input").unwrap();
+    let b = Day23::parse(&input).unwrap();
+    assert_eq!(solution(&b), 5366);
+    assert_eq!(solution2(&b), 29988);
+
+    let burrow = |rooms: [&str; 2]| {
+        let [top, bottom] = rooms.map(|r| r.chars().join("#"));
+        format!("#############\n#...........#\n###{top}###\n  #{bottom}#\n  #########")
+    };
+    let b = Day23::parse(&burrow(["ABCD", "ABCD"])).unwrap();
+    assert_eq!(solution(&b), 0);
+    // A waits left of room 1 so B can pass, 4 + 40 + 2.
+    let b = Day23::parse(&burrow(["BACD", "ABCD"])).unwrap();
+    assert_eq!(solution(&b), 46);
+
+    assert_eq!(
+        Day23::parse(&burrow(["ABCD", "ABCE"])).unwrap_err(),
+        AocError::new(10, "expected an amphipod from 'A' to 'D', found 'E'").on_line(4)
+    );
+    assert_eq!(
+        Day23::parse(&burrow(["ABCD", "ABCC"])).unwrap_err(),
+        AocError::new(1, "expected 2 amphipods of each type").on_line(3)
+    );
+    assert_eq!(
+        Day23::parse(&burrow(["ABCD", "ABCD"]).replace("#.", "#A")).unwrap_err(),
+        AocError::new(2, "expected '.'").on_line(2)
+    );
+    assert_eq!(
+        Day23::parse(&burrow(["ABCD", "ABCD"]).replace("D###", "D#")).unwrap_err(),
+        AocError::new(12, "expected '#'").on_line(3)
+    );
+    assert_eq!(
+        "#############\n#...........#\n###A#B#C#D###\n  #A#B#C#D#\n  #########"
+            .parse::<Burrow<4>>()
+            .unwrap_err(),
+        AocError::new(1, "expected 7 lines").on_line(6)
+    );
+}
+
+pub struct Day23;
+
+impl Solver for Day23 {
+    type Input<'a> = Burrow<2>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        input.parse()
+    }
+
+    fn part1(b: &Self::Input<'_>) -> usize {
+        solution(b)
+    }
+
+    fn part2(b: &Self::Input<'_>) -> usize {
+        solution2(b)
+    }
+}
@@ -1,22 +1,58 @@
+#[cfg(test)]
 use std::fs;
 
-fn update<const T: usize>(a: &[u32; T], v: &str) -> [u32; T] {
+use crate::{
+    error::{self, AocError},
+    solver::Solver,
+};
+
+// The diagnostic report, binary numbers that all have the same number of bits.
+#[derive(Debug)]
+pub struct Report<'a> {
+    bits: usize,
+    numbers: Vec<&'a str>,
+}
+
+impl<'a> Report<'a> {
+    pub fn parse(s: &'a str) -> Result<Self, AocError> {
+        let bits = s.lines().next().map_or(0, str::len);
+        if !(1..=32).contains(&bits) {
+            return Err(AocError::new(1, "expected a binary number of 1 to 32 bits"));
+        }
+        let numbers = error::lines(s, |l| {
+            if let Some((i, c)) = l.chars().enumerate().find(|(_, c)| !matches!(c, '0' | '1')) {
+                return Err(AocError::new(
+                    i + 1,
+                    format!("expected a bit, found '{}'", c.escape_default()),
+                ));
+            }
+            match l.len() {
+                n if n == bits => Ok(l),
+                n => Err(AocError::new(1, format!("expected {bits} bits, found {n}"))),
+            }
+        })?;
+        Ok(Self { bits, numbers })
+    }
+}
+
+fn update(a: &[u32], v: &str) -> Vec<u32> {
     (0..v.len())
         .map(|x| a[x] + ((v.as_bytes()[x] as u32) & 1))
-        .collect::<Vec<u32>>()
-        .try_into()
-        .unwrap()
+        .collect()
 }
 
-fn solution<const T: usize>(input: &str) -> u32 {
-    let (gamma, count) = input.lines().fold(([0u32; T], 0u32), |acc, x| {
-        (update::<T>(&acc.0, x), acc.1 + 1)
-    });
+pub fn solution(report: &Report) -> u32 {
+    let (gamma, count) = report
+        .numbers
+        .iter()
+        .fold((vec![0u32; report.bits], 0u32), |acc, x| {
+            (update(&acc.0, x), acc.1 + 1)
+        });
     let x: u32 = gamma
         .iter()
         .map(|x| (*x > count / 2) as u32)
         .fold(0u32, |acc, x| (acc << 1) | x);
-    x * ((!x) & ((1u32 << T) - 1))
+    x * ((!x) & (u32::MAX >> (32 - report.bits)))
 }
 
 fn run_for_column<'a>(input: impl Iterator<Item = &'a str>, n: usize) -> (u32, u32) {
@@ -30,24 +66,29 @@ enum Rating {
     Co2,
 }
 
-fn col2rec<'a, const T: usize>(
+fn col2rec<'a>(
     cols: impl Iterator<Item = &'a str> + Clone,
     rating: Rating,
     c: usize,
+    bits: usize,
 ) -> Vec<&'a str> {
-    if c == T {
+    if c == bits {
         return cols.collect::<Vec<&str>>();
     }
     let (bits_set, count) = run_for_column(cols.clone(), c);
     if count == 1 {
         return cols.collect::<Vec<&str>>();
     }
+    // A bit no number has is not the least common one, keep them all.
+    if bits_set == 0 || bits_set == count {
+        return col2rec(cols, rating, c + 1, bits);
+    }
     let (most_common, least_common) = if bits_set >= (count - bits_set) {
         ('1', '0')
     } else {
         ('0', '1')
     };
-    col2rec::<T>(
+    col2rec(
         cols.filter(|s| {
             s.as_bytes()[c]
                 == (match rating {
@@ -59,29 +100,63 @@ fn col2rec<'a, const T: usize>(
         .into_iter(),
         rating,
         c + 1,
+        bits,
     )
 }
 
-fn solution2<const T: usize>(input: &str) -> u32 {
-    let oxygen = u32::from_str_radix(col2rec::<T>(input.lines(), Rating::Oxygen, 0)[0], 2).unwrap();
-    let co2 = u32::from_str_radix(col2rec::<T>(input.lines(), Rating::Co2, 0)[0], 2).unwrap();
-    oxygen * co2
+// A rating of a report that is not empty, every filter keeps at least one number.
+fn rating(report: &Report, rating: Rating) -> u32 {
+    let numbers = report.numbers.iter().copied();
+    let rated = col2rec(numbers, rating, 0, report.bits)[0];
+    u32::from_str_radix(rated, 2).unwrap()
+}
+
+pub fn solution2(report: &Report) -> u32 {
+    rating(report, Rating::Oxygen) * rating(report, Rating::Co2)
 }
 
 #[test]
 fn test_run() {
     let input = fs::read_to_string("src/inputs/aoc_3_sample.input").unwrap();
-    assert_eq!(solution::<5>(&input), 198);
-    assert_eq!(solution2::<5>(&input), 230);
+    let report = Day3::parse(&input).unwrap();
+    assert_eq!(solution(&report), 198);
+    assert_eq!(solution2(&report), 230);
     let input = fs::read_to_string("src/inputs/aoc_3.input").unwrap();
-    assert_eq!(solution::<12>(&input), 2498354);
-    assert_eq!(solution2::<12>(&input), 3277956);
+    let report = Day3::parse(&input).unwrap();
+    assert_eq!(solution(&report), 2498354);
+    assert_eq!(solution2(&report), 3277956);
+    // All numbers start with 1, the CO2 rating must not filter them all out.
+    assert_eq!(solution2(&Day3::parse("100\n110\n111").unwrap()), 7 * 4);
+    assert_eq!(
+        Day3::parse("00100\n11210").unwrap_err(),
+        AocError::new(3, "expected a bit, found '2'").on_line(2)
+    );
+    assert_eq!(
+        Day3::parse("00100\n1111").unwrap_err(),
+        AocError::new(1, "expected 5 bits, found 4").on_line(2)
+    );
+    assert_eq!(
+        Day3::parse("").unwrap_err(),
+        AocError::new(1, "expected a binary number of 1 to 32 bits")
+    );
 }
 
-pub fn run() -> (String, String) {
-    let input = fs::read_to_string("src/inputs/aoc_3.input").unwrap();
-    (
-        solution::<12>(&input).to_string(),
-        solution2::<12>(&input).to_string(),
-    )
+pub struct Day3;
+
+impl Solver for Day3 {
+    type Input<'a> = Report<'a>;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        Report::parse(input)
+    }
+
+    fn part1(report: &Self::Input<'_>) -> u32 {
+        solution(report)
+    }
+
+    fn part2(report: &Self::Input<'_>) -> u32 {
+        solution2(report)
+    }
 }
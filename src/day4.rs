@@ -1,9 +1,14 @@
-use itertools::Itertools;
-use lazy_static::lazy_static;
-use regex::Regex;
+#[cfg(test)]
 use std::fs;
+use std::str::FromStr;
 
-#[derive(Debug)]
+use crate::{
+    error::{self, AocError},
+    input,
+    solver::Solver,
+};
+
+#[derive(Debug, Clone)]
 struct Board {
     numbers: [Vec<u32>; 5],
     hits: [Vec<bool>; 5],
@@ -11,16 +16,24 @@ struct Board {
 }
 
 impl Board {
-    pub fn new(s: [&str; 5]) -> Self {
-        lazy_static! {
-            static ref RE: Regex = Regex::new(r"\d+").unwrap();
-        }
-        Self {
-            numbers: s.map(|l| {
-                RE.find_iter(l)
-                    .map(|m| m.as_str().parse::<u32>().unwrap())
-                    .collect::<Vec<u32>>()
-            }),
+    // Errors are relative to the first row of the board.
+    pub fn new(s: [&str; 5]) -> Result<Self, AocError> {
+        let rows = s.iter().enumerate().map(|(i, l)| {
+            let row = l
+                .split_whitespace()
+                .map(|n| error::number(l, n))
+                .collect::<Result<Vec<u32>, AocError>>()
+                .map_err(|e| e.on_line(i + 1))?;
+            match row.len() {
+                5 => Ok(row),
+                n => Err(AocError::new(1, format!("expected 5 numbers, found {n}")).on_line(i + 1)),
+            }
+        });
+        Ok(Self {
+            numbers: rows
+                .collect::<Result<Vec<Vec<u32>>, AocError>>()?
+                .try_into()
+                .unwrap(),
             hits: [
                 vec![false; 5],
                 vec![false; 5],
@@ -29,7 +42,7 @@ impl Board {
                 vec![false; 5],
             ],
             bingo: false,
-        }
+        })
     }
 
     fn get_score(&self) -> u32 {
@@ -70,33 +83,46 @@ impl Board {
     }
 }
 
-#[derive(Debug)]
-struct Bingo {
+#[derive(Debug, Clone)]
+pub struct Bingo {
     numbers: Vec<u32>,
     boards: Vec<Board>,
 }
 
-impl Bingo {
-    pub fn new(s: &str) -> Self {
-        lazy_static! {
-            static ref RE: Regex = Regex::new(r"\d+").unwrap();
+impl FromStr for Bingo {
+    type Err = AocError;
+
+    // The drawn numbers, then boards of 5 rows, separated by blank lines.
+    fn from_str(s: &str) -> Result<Self, AocError> {
+        let paragraphs = input::paragraphs(s);
+        let first = paragraphs.first().map_or("", |(_, p)| p);
+        if first.lines().count() > 1 {
+            return Err(AocError::new(1, "expected a blank line").on_line(2));
         }
-        let mut lines = s.lines();
-        Self {
-            numbers: RE
-                .find_iter(lines.next().unwrap())
-                .map(|m| m.as_str().parse::<u32>().unwrap())
-                .collect(),
-            boards: lines
-                .chunks(6)
-                .into_iter()
-                .map(|mut chunk| {
-                    Board::new(chunk.next_chunk::<6>().unwrap()[1..].try_into().unwrap())
+        Ok(Self {
+            numbers: first
+                .split(',')
+                .map(|n| error::number(first, n))
+                .collect::<Result<Vec<u32>, AocError>>()?,
+            boards: paragraphs
+                .iter()
+                .skip(1)
+                .map(|(line, board)| {
+                    let rows = board.lines().collect::<Vec<&str>>();
+                    match rows.len() {
+                        5 => Board::new(rows.try_into().unwrap()).map_err(|e| e.on_line(*line)),
+                        n if n < 5 => {
+                            Err(AocError::new(1, "expected 5 board rows").on_line(line + n))
+                        }
+                        _ => Err(AocError::new(1, "expected a blank line").on_line(line + 5)),
+                    }
                 })
-                .collect(),
-        }
+                .collect::<Result<Vec<Board>, AocError>>()?,
+        })
     }
+}
 
+impl Bingo {
     fn play(&mut self) -> u32 {
         self.numbers
             .iter()
@@ -142,26 +168,72 @@ impl Bingo {
     }
 }
 
-fn solution(input: &str) -> u32 {
-    Bingo::new(input).play()
+pub fn solution(bingo: &Bingo) -> u32 {
+    bingo.clone().play()
 }
 
-fn solution2(input: &str) -> u32 {
-    Bingo::new(input).play2()
+pub fn solution2(bingo: &Bingo) -> u32 {
+    bingo.clone().play2()
 }
 
 #[test]
 fn test_run() {
     let sample_input = fs::read_to_string("src/inputs/aoc_4_sample.input").unwrap();
-    assert_eq!(solution(&sample_input), 4512);
-    assert_eq!(solution2(&sample_input), 1924);
+    let bingo = Day4::parse(&sample_input).unwrap();
+    assert_eq!(solution(&bingo), 4512);
+    assert_eq!(solution2(&bingo), 1924);
 
     let input = fs::read_to_string("src/inputs/aoc_4.input").unwrap();
-    assert_eq!(solution(&input), 10374);
-    assert_eq!(solution2(&input), 24742);
+    let bingo = Day4::parse(&input).unwrap();
+    assert_eq!(solution(&bingo), 10374);
+    assert_eq!(solution2(&bingo), 24742);
+
+    let board = "\n1 2 3 4 5\n1 2 3 4 5\n1 2 3 4 5\n1 2 3 4 5\n";
+    assert_eq!(
+        Day4::parse(&format!("7,4\n{board}1 2 3 4 5")).map(|b| b.boards.len()),
+        Ok(1)
+    );
+    assert_eq!(
+        Day4::parse(&format!("7,4\n{board}1 2 x 4 5")).unwrap_err(),
+        AocError::new(5, "expected a number, found 'x'").on_line(7)
+    );
+    assert_eq!(
+        Day4::parse(&format!("7,4\n{board}1 2 3 4")).unwrap_err(),
+        AocError::new(1, "expected 5 numbers, found 4").on_line(7)
+    );
+    assert_eq!(
+        Day4::parse(&format!("7,4\n{board}")).unwrap_err(),
+        AocError::new(1, "expected 5 board rows").on_line(7)
+    );
+    assert_eq!(
+        Day4::parse(&format!("7,4\n{board}1 2 3 4 5\n1 2 3 4 5")).unwrap_err(),
+        AocError::new(1, "expected a blank line").on_line(8)
+    );
+    // Boards may be separated by more than one blank line.
+    assert_eq!(
+        Day4::parse(&format!("7,4\n\n{board}1 2 3 4 5\n\n\n{board}1 2 3 4 5"))
+            .map(|b| b.boards.len()),
+        Ok(2)
+    );
+    assert!(Day4::parse("7;4").is_err());
 }
 
-pub fn run() -> (String, String) {
-    let input = fs::read_to_string("src/inputs/aoc_4.input").unwrap();
-    (solution(&input).to_string(), solution2(&input).to_string())
+pub struct Day4;
+
+impl Solver for Day4 {
+    type Input<'a> = Bingo;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        input.parse()
+    }
+
+    fn part1(bingo: &Self::Input<'_>) -> u32 {
+        solution(bingo)
+    }
+
+    fn part2(bingo: &Self::Input<'_>) -> u32 {
+        solution2(bingo)
+    }
 }
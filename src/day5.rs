@@ -1,6 +1,14 @@
-use lazy_static::lazy_static;
-use regex::{Captures, Regex};
-use std::{cmp, fs};
+use std::cmp;
+#[cfg(test)]
+use std::fs;
+use std::str::FromStr;
+
+use crate::{
+    compat,
+    error::{self, AocError},
+    grid::Grid,
+    solver::Solver,
+};
 
 #[derive(Debug)]
 struct Point {
@@ -8,22 +16,48 @@ struct Point {
     y: usize,
 }
 
+impl Point {
+    // `x,y` in `part` of `line`.
+    fn parse(line: &str, part: &str) -> Result<Self, AocError> {
+        let (x, y) = part
+            .split_once(',')
+            .ok_or_else(|| AocError::at(line, part, format!("expected 'x,y', found '{part}'")))?;
+        Ok(Point {
+            x: error::number(line, x)?,
+            y: error::number(line, y)?,
+        })
+    }
+}
+
 #[derive(Debug)]
-struct Line {
+pub struct Line {
     begin: Point,
     end: Point,
-    enable_diagonal: bool,
+}
+
+impl FromStr for Line {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, AocError> {
+        let (begin, end) = s
+            .split_once(" -> ")
+            .ok_or_else(|| AocError::new(1, format!("expected 'x,y -> x,y', found '{s}'")))?;
+        Ok(Line {
+            begin: Point::parse(s, begin)?,
+            end: Point::parse(s, end)?,
+        })
+    }
 }
 
 impl Line {
-    fn get_points(&self) -> Option<Vec<Point>> {
+    fn get_points(&self, enable_diagonal: bool) -> Option<Vec<Point>> {
         if self.begin.x == self.end.x {
-            let [a, b] = cmp::minmax(self.begin.y, self.end.y);
+            let [a, b] = compat::minmax(self.begin.y, self.end.y);
             Some((a..=b).map(|y| Point { x: self.begin.x, y }).collect())
         } else if self.begin.y == self.end.y {
-            let [a, b] = cmp::minmax(self.begin.x, self.end.x);
+            let [a, b] = compat::minmax(self.begin.x, self.end.x);
             Some((a..=b).map(|x| Point { x, y: self.begin.y }).collect())
-        } else if self.enable_diagonal {
+        } else if enable_diagonal {
             let length = (self.begin.y as i32 - self.end.y as i32).abs();
             let y_dir = (self.end.y as i32 - self.begin.y as i32).signum();
             let x_dir = (self.end.x as i32 - self.begin.x as i32).signum();
@@ -43,17 +77,18 @@ impl Line {
 
 #[derive(Debug)]
 struct Board {
-    tiles: Vec<Vec<u32>>,
+    tiles: Grid<u32>,
 }
 
-fn draw_line(tiles: &mut [Vec<u32>], line: &Line) {
-    if let Some(p) = line.get_points() {
-        p.iter().for_each(|p| tiles[p.y][p.x] += 1)
+fn draw_line(tiles: &mut Grid<u32>, line: &Line, enable_diagonal: bool) {
+    if let Some(p) = line.get_points(enable_diagonal) {
+        p.iter().for_each(|p| tiles[(p.x, p.y)] += 1)
     }
 }
 
 impl Board {
-    fn new(lines: &[Line]) -> Self {
+    // `lines` must not be empty.
+    fn new(lines: &[Line], enable_diagonal: bool) -> Self {
         let max_x = lines
             .iter()
             .map(|l| cmp::max(l.begin.x, l.end.x))
@@ -64,70 +99,70 @@ impl Board {
             .map(|l| cmp::max(l.begin.y, l.end.y))
             .max()
             .unwrap();
-        let mut tiles = vec![vec![0; max_x + 1]; max_y + 1];
-        lines.iter().for_each(|line| draw_line(&mut tiles, line));
+        let mut tiles = Grid::new(max_x + 1, max_y + 1, 0);
+        lines
+            .iter()
+            .for_each(|line| draw_line(&mut tiles, line, enable_diagonal));
         Board { tiles }
     }
 
     fn get_score(&self) -> u32 {
-        self.tiles
-            .iter()
-            .map(|row| {
-                row.iter()
-                    .map(|v| if *v > 1 { 1u32 } else { 0u32 })
-                    .sum::<u32>()
-            })
-            .sum()
+        self.tiles.iter().filter(|(_, v)| **v > 1).count() as u32
     }
 }
 
-fn get_usize(caps: &Captures, n: usize) -> usize {
-    caps.get(n)
-        .map(|v| str::parse(v.as_str()).unwrap())
-        .unwrap()
+pub fn parse(input: &str) -> Result<Vec<Line>, AocError> {
+    let lines = error::parse_lines(input)?;
+    if lines.is_empty() {
+        return Err(AocError::new(1, "expected a line of vents"));
+    }
+    Ok(lines)
 }
 
-fn solution(input: &str, enable_diagonal: bool) -> u32 {
-    lazy_static! {
-        static ref RE: Regex = Regex::new(r"(\d+),(\d+) -> (\d+),(\d+)").unwrap();
-    }
-    let board = Board::new(
-        &input
-            .lines()
-            .map(|l| {
-                RE.captures(l)
-                    .map(|caps| Line {
-                        begin: Point {
-                            x: get_usize(&caps, 1),
-                            y: get_usize(&caps, 2),
-                        },
-                        end: Point {
-                            x: get_usize(&caps, 3),
-                            y: get_usize(&caps, 4),
-                        },
-                        enable_diagonal,
-                    })
-                    .unwrap()
-            })
-            .collect::<Vec<Line>>(),
-    );
-    board.get_score()
+pub fn solution(lines: &[Line], enable_diagonal: bool) -> u32 {
+    Board::new(lines, enable_diagonal).get_score()
 }
 
 #[test]
 fn test_run() {
     let input = fs::read_to_string("src/inputs/aoc_5_sample.input").unwrap();
-    assert_eq!(solution(&input, false), 5);
-    assert_eq!(solution(&input, true), 12);
+    let lines = Day5::parse(&input).unwrap();
+    assert_eq!(solution(&lines, false), 5);
+    assert_eq!(solution(&lines, true), 12);
     let input = fs::read_to_string("src/inputs/aoc_5.input").unwrap();
-    assert_eq!(solution(&input, false), 5698);
-    assert_eq!(solution(&input, true), 15463);
+    let lines = Day5::parse(&input).unwrap();
+    assert_eq!(solution(&lines, false), 5698);
+    assert_eq!(solution(&lines, true), 15463);
+    assert_eq!(
+        Day5::parse("0,9 -> 5,9\n8,0 -> 0,x").unwrap_err(),
+        AocError::new(10, "expected a number, found 'x'").on_line(2)
+    );
+    assert_eq!(
+        Day5::parse("0,9 -> 5,9\n8,0 0,8").unwrap_err(),
+        AocError::new(1, "expected 'x,y -> x,y', found '8,0 0,8'").on_line(2)
+    );
+    assert_eq!(
+        Day5::parse("").unwrap_err(),
+        AocError::new(1, "expected a line of vents")
+    );
 }
 
-pub fn run() -> (String, String) {
-    let input = fs::read_to_string("src/inputs/aoc_5.input").unwrap();
-    (
-        solution(&input, false).to_string(),
-        solution(&input, true).to_string(),
-    )
+pub struct Day5;
+
+impl Solver for Day5 {
+    type Input<'a> = Vec<Line>;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        parse(input)
+    }
+
+    fn part1(lines: &Self::Input<'_>) -> u32 {
+        solution(lines, false)
+    }
+
+    fn part2(lines: &Self::Input<'_>) -> u32 {
+        solution(lines, true)
+    }
 }
@@ -1,6 +1,14 @@
-use lazy_static::lazy_static;
-use regex::Regex;
-use std::{collections::HashMap, fs};
+use std::collections::HashMap;
+#[cfg(test)]
+use std::fs;
+
+use crate::{
+    error::{self, AocError},
+    solver::Solver,
+};
+
+// A fish's timer starts at this when it is born.
+const MAX_TIMER: i32 = 8;
 
 #[derive(Debug, Clone)]
 struct Fish {
@@ -43,6 +51,7 @@ impl Population {
     }
 }
 
+// The number of fish a single fish with each timer up to `max_days` turns into.
 fn simulate_population(max_days: i32, days_to_pass: i32) -> HashMap<i32, i64> {
     let mut p = Population {
         p: vec![
@@ -56,7 +65,7 @@ fn simulate_population(max_days: i32, days_to_pass: i32) -> HashMap<i32, i64> {
     (0..days_to_pass - max_days).for_each(|_| {
         p.daypass();
     });
-    (0..max_days)
+    (0..=max_days)
         .map(|i| {
             p.daypass();
             (max_days - i, p.get_size())
@@ -64,31 +73,75 @@ fn simulate_population(max_days: i32, days_to_pass: i32) -> HashMap<i32, i64> {
         .collect()
 }
 
-fn solution(input: &str, days: i32) -> i64 {
-    lazy_static! {
-        static ref RE: Regex = Regex::new(r"\d+").unwrap();
+// A line of comma separated timers.
+pub fn parse(input: &str) -> Result<Vec<i32>, AocError> {
+    let lines = error::lines(input, |l| {
+        l.split(',')
+            .map(|n| match error::number(l, n)? {
+                t @ 0..=MAX_TIMER => Ok(t),
+                _ => Err(AocError::at(
+                    l,
+                    n,
+                    format!("expected a timer from 0 to {MAX_TIMER}, found '{n}'"),
+                )),
+            })
+            .collect::<Result<Vec<i32>, AocError>>()
+    })?;
+    match &lines[..] {
+        [timers] => Ok(timers.clone()),
+        _ => Err(AocError::new(1, "expected a single line of timers")),
     }
-    let cache: HashMap<i32, i64> = simulate_population(5, days);
-    RE.find_iter(input)
-        .map(|m| cache.get(&m.as_str().parse::<i32>().unwrap()).unwrap())
-        .sum()
+}
+
+pub fn solution(timers: &[i32], days: i32) -> i64 {
+    let cache: HashMap<i32, i64> = simulate_population(MAX_TIMER, days);
+    timers.iter().map(|t| cache[t]).sum()
 }
 
 #[test]
 fn test_run() {
     let input = fs::read_to_string("src/inputs/aoc_6_sample.input").unwrap();
-    assert_eq!(solution(&input, 18), 26);
-    assert_eq!(solution(&input, 80), 5934);
-    assert_eq!(solution(&input, 256), 26984457539);
+    let timers = Day6::parse(&input).unwrap();
+    assert_eq!(solution(&timers, 18), 26);
+    assert_eq!(solution(&timers, 80), 5934);
+    assert_eq!(solution(&timers, 256), 26984457539);
     let input = fs::read_to_string("src/inputs/aoc_6.input").unwrap();
-    assert_eq!(solution(&input, 80), 353079);
-    assert_eq!(solution(&input, 256), 1605400130036);
+    let timers = Day6::parse(&input).unwrap();
+    assert_eq!(solution(&timers, 80), 353079);
+    assert_eq!(solution(&timers, 256), 1605400130036);
+    // Newborn fish and fish about to give birth.
+    assert_eq!(solution(&[8], 18), solution(&[1], 11));
+    assert_eq!(solution(&[0], 18), solution(&[1], 19));
+    assert_eq!(
+        Day6::parse("3,4,x").unwrap_err(),
+        AocError::new(5, "expected a number, found 'x'")
+    );
+    assert_eq!(
+        Day6::parse("3,9").unwrap_err(),
+        AocError::new(3, "expected a timer from 0 to 8, found '9'")
+    );
+    assert_eq!(
+        Day6::parse("").unwrap_err(),
+        AocError::new(1, "expected a single line of timers")
+    );
 }
 
-pub fn run() -> (String, String) {
-    let input = fs::read_to_string("src/inputs/aoc_6.input").unwrap();
-    (
-        solution(&input, 80).to_string(),
-        solution(&input, 256).to_string(),
-    )
+pub struct Day6;
+
+impl Solver for Day6 {
+    type Input<'a> = Vec<i32>;
+    type Output1 = i64;
+    type Output2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        parse(input)
+    }
+
+    fn part1(timers: &Self::Input<'_>) -> i64 {
+        solution(timers, 80)
+    }
+
+    fn part2(timers: &Self::Input<'_>) -> i64 {
+        solution(timers, 256)
+    }
 }
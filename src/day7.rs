@@ -1,15 +1,26 @@
-use lazy_static::lazy_static;
-use regex::Regex;
+#[cfg(test)]
 use std::fs;
 
-fn solution(input: &str, fuel: fn(u32) -> usize) -> usize {
-    lazy_static! {
-        static ref RE: Regex = Regex::new(r"\d+").unwrap();
+use crate::{
+    error::{self, AocError},
+    solver::Solver,
+};
+
+// A line of comma separated crab positions.
+pub fn parse(input: &str) -> Result<Vec<u32>, AocError> {
+    let lines = error::lines(input, |l| {
+        l.split(',')
+            .map(|n| error::number(l, n))
+            .collect::<Result<Vec<u32>, AocError>>()
+    })?;
+    match &lines[..] {
+        [crabs] => Ok(crabs.clone()),
+        _ => Err(AocError::new(1, "expected a single line of positions")),
     }
-    let crabs = RE
-        .find_iter(input)
-        .map(|m| m.as_str().parse::<u32>().unwrap())
-        .collect::<Vec<u32>>();
+}
+
+// `crabs` must not be empty.
+pub fn solution(crabs: &[u32], fuel: fn(u32) -> usize) -> usize {
     let max_crab = crabs.iter().max().unwrap();
     (0..=*max_crab)
         .map(|pos| {
@@ -22,25 +33,50 @@ fn solution(input: &str, fuel: fn(u32) -> usize) -> usize {
         .unwrap()
 }
 
-fn solution1(input: &str) -> usize {
-    solution(input, |x| x as usize)
+pub fn solution1(crabs: &[u32]) -> usize {
+    solution(crabs, |x| x as usize)
 }
 
-fn solution2(input: &str) -> usize {
-    solution(input, |x| (((1 + x) * x) / 2) as usize)
+pub fn solution2(crabs: &[u32]) -> usize {
+    solution(crabs, |x| (((1 + x) * x) / 2) as usize)
 }
 
 #[test]
 fn test_run() {
     let input = fs::read_to_string("src/inputs/aoc_7_sample.input").unwrap();
-    assert_eq!(solution1(&input), 37);
-    assert_eq!(solution2(&input), 168);
+    let crabs = Day7::parse(&input).unwrap();
+    assert_eq!(solution1(&crabs), 37);
+    assert_eq!(solution2(&crabs), 168);
     let input = fs::read_to_string("src/inputs/aoc_7.input").unwrap();
-    assert_eq!(solution1(&input), 339321);
-    assert_eq!(solution2(&input), 95476244);
+    let crabs = Day7::parse(&input).unwrap();
+    assert_eq!(solution1(&crabs), 339321);
+    assert_eq!(solution2(&crabs), 95476244);
+    assert_eq!(
+        Day7::parse("16,1,-2").unwrap_err(),
+        AocError::new(6, "expected a number, found '-2'")
+    );
+    assert_eq!(
+        Day7::parse("").unwrap_err(),
+        AocError::new(1, "expected a single line of positions")
+    );
 }
 
-pub fn run() -> (String, String) {
-    let input = fs::read_to_string("src/inputs/aoc_7.input").unwrap();
-    (solution1(&input).to_string(), solution2(&input).to_string())
+pub struct Day7;
+
+impl Solver for Day7 {
+    type Input<'a> = Vec<u32>;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        parse(input)
+    }
+
+    fn part1(crabs: &Self::Input<'_>) -> usize {
+        solution1(crabs)
+    }
+
+    fn part2(crabs: &Self::Input<'_>) -> usize {
+        solution2(crabs)
+    }
 }
@@ -1,67 +1,126 @@
 use itertools::Itertools;
-use lazy_static::lazy_static;
-use regex::Regex;
-use std::{
-    collections::{HashMap, HashSet},
-    fs,
+use std::collections::{HashMap, HashSet};
+#[cfg(test)]
+use std::fs;
+use std::str::FromStr;
+
+use crate::{
+    error::{self, AocError},
+    solver::Solver,
 };
 
-fn solution(input: &str) -> usize {
-    lazy_static! {
-        static ref RE: Regex = Regex::new(r"[a-g]+").unwrap();
-    }
-    input
-        .lines()
-        .map(|l| {
-            RE.find_iter(l)
-                .skip(10)
-                .filter(|d| [2, 3, 4, 7].contains(&d.as_str().len()))
-                .count()
-        })
-        .sum()
-}
+// The number of segments lit for each of the digits 0 to 9.
+const SEGMENTS: [usize; 10] = [6, 2, 5, 5, 4, 5, 6, 3, 7, 6];
 
+#[derive(Debug)]
 struct Digit {
     s: String,
     hs: HashSet<char>,
 }
 
-struct ParsedInput {
+impl Digit {
+    // The segments `a` to `g` in `part` of `line`, each at most once.
+    fn parse(line: &str, part: &str) -> Result<Self, AocError> {
+        if let Some(c) = part.chars().find(|c| !('a'..='g').contains(c)) {
+            return Err(AocError::at(
+                line,
+                part,
+                format!("expected segments a to g, found '{}'", c.escape_default()),
+            ));
+        }
+        let hs = HashSet::<char>::from_iter(part.chars());
+        if hs.len() != part.len() {
+            return Err(AocError::at(
+                line,
+                part,
+                format!("repeated segment in '{part}'"),
+            ));
+        }
+        Ok(Digit {
+            s: part.chars().sorted().collect::<String>(),
+            hs,
+        })
+    }
+}
+
+// The ten unique signal patterns and the four digit output value of a display.
+#[derive(Debug)]
+pub struct Entry {
     digits: Vec<Digit>,
     output_values: Vec<Digit>,
 }
 
-impl ParsedInput {
-    fn from_tuple(t: (Vec<Digit>, Vec<Digit>)) -> Self {
-        Self {
-            digits: t.0,
-            output_values: t.1,
+impl FromStr for Entry {
+    type Err = AocError;
+
+    fn from_str(l: &str) -> Result<Self, AocError> {
+        let (patterns, output) = l.split_once(" | ").ok_or_else(|| {
+            AocError::new(
+                1,
+                format!("expected '<10 patterns> | <4 digits>', found '{l}'"),
+            )
+        })?;
+        let digits = patterns
+            .split(' ')
+            .map(|p| Digit::parse(l, p))
+            .collect::<Result<Vec<Digit>, AocError>>()?;
+        let lengths = digits
+            .iter()
+            .map(|d| d.s.len())
+            .sorted()
+            .collect::<Vec<_>>();
+        if !SEGMENTS.iter().copied().sorted().eq(lengths)
+            || !digits.iter().map(|d| &d.s).all_unique()
+        {
+            return Err(AocError::at(
+                l,
+                patterns,
+                "expected a pattern for each of the ten digits",
+            ));
         }
+        let output_values = output
+            .split(' ')
+            .map(|o| {
+                let d = Digit::parse(l, o)?;
+                match digits.iter().any(|p| p.s == d.s) {
+                    true => Ok(d),
+                    false => Err(AocError::at(l, o, format!("unknown pattern '{o}'"))),
+                }
+            })
+            .collect::<Result<Vec<Digit>, AocError>>()?;
+        if output_values.len() != 4 {
+            return Err(AocError::at(l, output, "expected 4 output digits"));
+        }
+        Ok(Entry {
+            digits,
+            output_values,
+        })
     }
 }
-type DigitMap<'a> = HashMap<&'a String, (u32, &'a HashSet<char>)>;
 
-fn parse_line(l: &str) -> ParsedInput {
-    lazy_static! {
-        static ref RE: Regex = Regex::new(r"[a-g]+").unwrap();
+pub fn parse(input: &str) -> Result<Vec<Entry>, AocError> {
+    let entries = error::parse_lines(input)?;
+    if entries.is_empty() {
+        return Err(AocError::new(1, "expected a display entry"));
     }
-    ParsedInput::from_tuple(
-        l.split('|')
-            .take(2)
-            .map(|s| {
-                RE.find_iter(s)
-                    .map(|t| Digit {
-                        s: t.as_str().chars().sorted().collect::<String>(),
-                        hs: HashSet::<char>::from_iter(t.as_str().chars()),
-                    })
-                    .collect::<Vec<Digit>>()
-            })
-            .collect_tuple()
-            .unwrap(),
-    )
+    Ok(entries)
 }
 
-fn map1478(pi: &ParsedInput) -> DigitMap {
+pub fn solution(entries: &[Entry]) -> usize {
+    entries
+        .iter()
+        .map(|e| {
+            e.output_values
+                .iter()
+                .filter(|d| [2, 3, 4, 7].contains(&d.s.len()))
+                .count()
+        })
+        .sum()
+}
+
+type DigitMap<'a> = HashMap<&'a String, (u32, &'a HashSet<char>)>;
+
+fn map1478(pi: &Entry) -> DigitMap<'_> {
     pi.digits
         .iter()
         .filter_map(|d| match d.s.len() {
@@ -74,7 +133,7 @@ fn map1478(pi: &ParsedInput) -> DigitMap {
         .collect::<DigitMap>()
 }
 
-fn map235<'a>(pi: &'a ParsedInput, digit_map: &DigitMap) -> DigitMap<'a> {
+fn map235<'a>(pi: &'a Entry, digit_map: &DigitMap) -> DigitMap<'a> {
     let rmap = digit_map
         .iter()
         .map(|(_, (d, hs))| (d, hs))
@@ -102,7 +161,7 @@ fn map235<'a>(pi: &'a ParsedInput, digit_map: &DigitMap) -> DigitMap<'a> {
         .collect::<DigitMap>()
 }
 
-fn map069<'a>(pi: &'a ParsedInput, digit_map: &DigitMap) -> DigitMap<'a> {
+fn map069<'a>(pi: &'a Entry, digit_map: &DigitMap) -> DigitMap<'a> {
     let rmap = digit_map
         .iter()
         .map(|(_, (d, hs))| (d, hs))
@@ -128,32 +187,71 @@ fn map069<'a>(pi: &'a ParsedInput, digit_map: &DigitMap) -> DigitMap<'a> {
         .collect::<DigitMap>()
 }
 
-fn process_line(l: &str) -> u32 {
-    let pi = parse_line(l);
-    let mut digit_map = map1478(&pi);
-    digit_map.extend(map235(&pi, &digit_map));
-    digit_map.extend(map069(&pi, &digit_map));
+fn process_line(pi: &Entry) -> u32 {
+    let mut digit_map = map1478(pi);
+    digit_map.extend(map235(pi, &digit_map));
+    digit_map.extend(map069(pi, &digit_map));
     pi.output_values
         .iter()
         .map(|d| digit_map.get(&d.s).unwrap().0)
         .fold(0, |acc, v| acc * 10 + v)
 }
 
-fn solution2(input: &str) -> u32 {
-    input.lines().map(process_line).sum()
+pub fn solution2(entries: &[Entry]) -> u32 {
+    entries.iter().map(process_line).sum()
 }
 
 #[test]
 fn test_run() {
     let input = fs::read_to_string("src/inputs/aoc_8_sample.input").unwrap();
-    assert_eq!(solution(&input), 26);
-    assert_eq!(solution2(&input), 61229);
+    let entries = Day8::parse(&input).unwrap();
+    assert_eq!(solution(&entries), 26);
+    assert_eq!(solution2(&entries), 61229);
     let input = fs::read_to_string("src/inputs/aoc_8.input").unwrap();
-    assert_eq!(solution(&input), 330);
-    assert_eq!(solution2(&input), 1010472);
+    let entries = Day8::parse(&input).unwrap();
+    assert_eq!(solution(&entries), 330);
+    assert_eq!(solution2(&entries), 1010472);
+    let patterns = "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab";
+    assert!(Day8::parse(&format!("{patterns} | cdfeb fcadb cdfeb cdbaf")).is_ok());
+    assert_eq!(
+        Day8::parse(&format!("{patterns} | cdfeb fcadb cdfeb cdbah")).unwrap_err(),
+        AocError::new(80, "expected segments a to g, found 'h'")
+    );
+    assert_eq!(
+        Day8::parse(&format!("{patterns} | cdfeb fcadb cdfeb")).unwrap_err(),
+        AocError::new(62, "expected 4 output digits")
+    );
+    assert_eq!(
+        Day8::parse(&format!("{patterns} | cdfeb fcadb cdfeb cdbag")).unwrap_err(),
+        AocError::new(80, "unknown pattern 'cdbag'")
+    );
+    assert_eq!(
+        Day8::parse("acedgfb cdfbe | cdfeb").unwrap_err(),
+        AocError::new(1, "expected a pattern for each of the ten digits")
+    );
+    assert!(Day8::parse(patterns).is_err());
+    assert_eq!(
+        Day8::parse("").unwrap_err(),
+        AocError::new(1, "expected a display entry")
+    );
 }
 
-pub fn run() -> (String, String) {
-    let input = fs::read_to_string("src/inputs/aoc_8.input").unwrap();
-    (solution(&input).to_string(), solution2(&input).to_string())
+pub struct Day8;
+
+impl Solver for Day8 {
+    type Input<'a> = Vec<Entry>;
+    type Output1 = usize;
+    type Output2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        parse(input)
+    }
+
+    fn part1(entries: &Self::Input<'_>) -> usize {
+        solution(entries)
+    }
+
+    fn part2(entries: &Self::Input<'_>) -> u32 {
+        solution2(entries)
+    }
 }
@@ -1,110 +1,111 @@
-use std::collections::VecDeque;
+#[cfg(test)]
 use std::fs;
+use std::{collections::VecDeque, str::FromStr};
+
+use crate::{
+    error::AocError,
+    grid::{self, Pos},
+    solver::Solver,
+};
 
 #[derive(Debug)]
-struct Grid {
-    g: Vec<Vec<u8>>,
+pub struct Grid {
+    g: grid::Grid<u8>,
 }
 
-impl From<&str> for Grid {
-    fn from(s: &str) -> Self {
-        Self {
-            g: s.lines()
-                .map(|l| l.chars().map(|c| c as u8 - 0x30).collect())
-                .collect::<Vec<Vec<u8>>>(),
+impl FromStr for Grid {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, AocError> {
+        let g = grid::Grid::digits(s)?;
+        if g.width() == 0 {
+            return Err(AocError::new(1, "expected a row of heights"));
         }
+        Ok(Self { g })
     }
 }
 
 impl Grid {
-    fn is_lowest(&self, x: usize, y: usize) -> bool {
-        self.get_surroundings(x, y)
-            .all(|(nx, ny)| self.g[ny][nx] > self.g[y][x])
+    fn is_lowest(&self, p: Pos) -> bool {
+        self.g.neighbours4(p).all(|n| self.g[n] > self.g[p])
     }
 
-    fn get_lowest(&self) -> Vec<(usize, usize)> {
-        (0..self.g.len())
-            .flat_map(|y| {
-                (0..self.g[y].len())
-                    .filter(|x| self.is_lowest(*x, y))
-                    .map(|x| (x, y))
-                    .collect::<Vec<(usize, usize)>>()
-            })
-            .collect::<Vec<(usize, usize)>>()
+    fn get_lowest(&self) -> Vec<Pos> {
+        self.g.positions().filter(|p| self.is_lowest(*p)).collect()
     }
 
-    fn get_score1(&self) -> u32 {
+    pub fn get_score1(&self) -> u32 {
         self.get_lowest()
             .iter()
-            .fold(0u32, |acc, (x, y)| acc + self.g[*y][*x] as u32 + 1)
-    }
-
-    fn get_surroundings(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
-        [(0, -1), (0, 1), (-1, 0), (1, 0)]
-            .iter()
-            .filter_map(move |(xd, yd)| {
-                let nx = x as i32 + xd;
-                let ny = y as i32 + yd;
-                if nx < 0
-                    || ny < 0
-                    || ny as usize >= self.g.len()
-                    || nx as usize >= self.g[ny as usize].len()
-                {
-                    None
-                } else {
-                    Some((nx as usize, ny as usize))
-                }
-            })
+            .fold(0u32, |acc, p| acc + self.g[*p] as u32 + 1)
     }
 
-    fn get_non9(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
-        self.get_surroundings(x, y)
-            .filter(|(xs, ys)| self.g[*ys][*xs] != 9)
+    fn get_non9(&self, p: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.g.neighbours4(p).filter(|n| self.g[*n] != 9)
     }
 
-    fn get_basin_size(&self, x: usize, y: usize) -> u32 {
-        let mut visited = vec![vec![false; self.g[0].len()]; self.g.len()];
-        let mut q: VecDeque<(usize, usize)> = VecDeque::new();
+    fn get_basin_size(&self, p: Pos) -> u32 {
+        let mut visited = grid::Grid::new(self.g.width(), self.g.height(), false);
+        let mut q: VecDeque<Pos> = VecDeque::new();
         let mut basin_size = 0;
-        q.push_back((x, y));
+        q.push_back(p);
         while !q.is_empty() {
-            let (x, y) = q.pop_front().unwrap();
-            if visited[y][x] {
+            let p = q.pop_front().unwrap();
+            if visited[p] {
                 continue;
             }
-            visited[y][x] = true;
+            visited[p] = true;
             basin_size += 1;
-            q.extend(self.get_non9(x, y).filter(|(x, y)| !visited[*y][*x]))
+            q.extend(self.get_non9(p).filter(|n| !visited[*n]))
         }
         basin_size
     }
 
-    fn get_score2(&self) -> u32 {
+    pub fn get_score2(&self) -> u32 {
         let mut lowest = self
             .get_lowest()
             .iter()
-            .map(|(x, y)| self.get_basin_size(*x, *y))
+            .map(|p| self.get_basin_size(*p))
             .collect::<Vec<u32>>();
         lowest.sort_by(|a, b| b.cmp(a));
         lowest.iter().take(3).product()
     }
 }
 
-fn solution(input: &str) -> (u32, u32) {
-    let g = Grid::from(input);
-    (g.get_score1(), g.get_score2())
-}
-
 #[test]
 fn test_run() {
     let input = fs::read_to_string("src/inputs/aoc_9_sample.input").unwrap();
-    assert_eq!(solution(&input), (15, 1134));
+    let g = Day9::parse(&input).unwrap();
+    assert_eq!((g.get_score1(), g.get_score2()), (15, 1134));
     let input = fs::read_to_string("src/inputs/aoc_9.input").unwrap();
-    assert_eq!(solution(&input), (522, 916688));
+    let g = Day9::parse(&input).unwrap();
+    assert_eq!((g.get_score1(), g.get_score2()), (522, 916688));
+    assert_eq!(
+        Day9::parse("219\n3-8").unwrap_err(),
+        AocError::new(2, "expected a digit, found '-'").on_line(2)
+    );
+    assert_eq!(
+        Day9::parse("").unwrap_err(),
+        AocError::new(1, "expected a row of heights")
+    );
 }
 
-pub fn run() -> (String, String) {
-    let input = fs::read_to_string("src/inputs/aoc_9.input").unwrap();
-    let (p1, p2) = solution(&input);
-    (p1.to_string(), p2.to_string())
+pub struct Day9;
+
+impl Solver for Day9 {
+    type Input<'a> = Grid;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        input.parse()
+    }
+
+    fn part1(g: &Self::Input<'_>) -> u32 {
+        g.get_score1()
+    }
+
+    fn part2(g: &Self::Input<'_>) -> u32 {
+        g.get_score2()
+    }
 }
@@ -0,0 +1,134 @@
+use std::{error::Error, fmt, str::FromStr};
+
+// A malformed puzzle input, `line` and `column` are 1-based and count characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AocError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl AocError {
+    // An error at `column` of a single line, see `on_line` to move it.
+    pub fn new(column: usize, message: impl Into<String>) -> Self {
+        Self {
+            line: 1,
+            column,
+            message: message.into(),
+        }
+    }
+
+    // An error at `part`, which must be a slice of `line`.
+    pub fn at(line: &str, part: &str, message: impl Into<String>) -> Self {
+        Self::new(column(line, part), message)
+    }
+
+    // Moves an error relative to a line or paragraph to `line` of the enclosing input.
+    pub fn on_line(self, line: usize) -> Self {
+        Self {
+            line: self.line + line - 1,
+            ..self
+        }
+    }
+
+    // The error followed by the offending line of `input` and a caret under the column.
+    pub fn diagnostic(&self, input: &str) -> String {
+        let text = input.lines().nth(self.line - 1).unwrap_or_default();
+        let width = self.line.to_string().len();
+        format!(
+            "{self}\n{:>width$} | {text}\n{:>width$} | {}^",
+            self.line,
+            "",
+            " ".repeat(self.column.saturating_sub(1))
+        )
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl Error for AocError {}
+
+// 1-based column of `part` in `line`, `part` must be a slice of `line`.
+pub fn column(line: &str, part: &str) -> usize {
+    let offset = (part.as_ptr() as usize).saturating_sub(line.as_ptr() as usize);
+    line.get(..offset).map_or(1, |s| s.chars().count() + 1)
+}
+
+// Parses `part` of `line` as a number.
+pub fn number<T: FromStr>(line: &str, part: &str) -> Result<T, AocError> {
+    part.trim()
+        .parse()
+        .map_err(|_| AocError::at(line, part, format!("expected a number, found '{part}'")))
+}
+
+// Parses the digit `c` found at `column`.
+pub fn digit(c: char, column: usize) -> Result<u8, AocError> {
+    c.to_digit(10).map(|d| d as u8).ok_or_else(|| {
+        AocError::new(
+            column,
+            format!("expected a digit, found '{}'", c.escape_default()),
+        )
+    })
+}
+
+// Parses a line of digits.
+pub fn digits(line: &str) -> Result<Vec<u8>, AocError> {
+    line.chars()
+        .enumerate()
+        .map(|(i, c)| digit(c, i + 1))
+        .collect()
+}
+
+// Parses each line of `input` with `f`, errors are moved to the line they occurred on.
+pub fn lines<'a, T>(
+    input: &'a str,
+    f: impl Fn(&'a str) -> Result<T, AocError>,
+) -> Result<Vec<T>, AocError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(n, l)| f(l).map_err(|e| e.on_line(n + 1)))
+        .collect()
+}
+
+// Like `lines`, for types that parse a single line.
+pub fn parse_lines<T: FromStr<Err = AocError>>(input: &str) -> Result<Vec<T>, AocError> {
+    lines(input, str::parse)
+}
+
+#[test]
+fn test_error() {
+    let line = "forward x";
+    let e = number::<i32>(line, &line[8..]).unwrap_err();
+    assert_eq!(e, AocError::new(9, "expected a number, found 'x'"));
+    assert_eq!(
+        e.to_string(),
+        "line 1, column 9: expected a number, found 'x'"
+    );
+    assert_eq!(column(line, line), 1);
+    assert_eq!(
+        lines("1\n2\nx3", |l| number::<u8>(l, l)).unwrap_err(),
+        AocError::new(1, "expected a number, found 'x3'").on_line(3)
+    );
+    assert_eq!(lines("1\n2", |l| number::<u8>(l, l)), Ok(vec![1, 2]));
+    assert_eq!(digits("123"), Ok(vec![1, 2, 3]));
+    assert_eq!(
+        digits("12\r").unwrap_err(),
+        AocError::new(3, "expected a digit, found '\\r'")
+    );
+    let input = "1\n2\n3\n4\n5\n6\n7\n8\n9\nforward x";
+    assert_eq!(
+        e.on_line(10).diagnostic(input),
+        "line 10, column 9: expected a number, found 'x'
+10 | forward x
+   |         ^"
+    );
+}
@@ -0,0 +1,716 @@
+// Random puzzle inputs for scale testing, see `generate`. Every generator keeps the guarantees
+// the puzzle text makes about real inputs, so a solver failing on one has a bug.
+use std::collections::HashSet;
+
+use crate::{
+    day23::Burrow,
+    geometry::{Point, Rotation, Vector},
+};
+
+// SplitMix64, small and good enough for test data. The same seed always gives the same input.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // Uniform in `0..n`, `n` must not be zero.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n != 0, "empty range");
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+
+    // Uniform in `lo..=hi`.
+    pub fn range(&mut self, lo: usize, hi: usize) -> usize {
+        lo + self.below(hi - lo + 1)
+    }
+
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64) < p * (1u64 << 53) as f64
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        (1..items.len())
+            .rev()
+            .for_each(|i| items.swap(i, self.below(i + 1)));
+    }
+}
+
+type Generator = fn(&mut Rng, usize) -> String;
+
+// The generator of a day and its default size, which is about that of a real input. What the
+// size counts differs per day, see the comment of each generator.
+fn generator(day: u8) -> Option<(usize, Generator)> {
+    Some(match day {
+        1 => (2000, depths),
+        2 => (1000, commands),
+        3 => (1000, diagnostics),
+        4 => (100, bingo),
+        5 => (500, vents),
+        6 => (300, lanternfish),
+        7 => (1000, crabs),
+        8 => (200, displays),
+        9 => (100, heightmap),
+        10 => (100, navigation),
+        11 => (10, octopuses),
+        12 => (10, caves),
+        13 => (900, manual),
+        14 => (20, polymer),
+        15 => (100, risks),
+        16 => (100, transmission),
+        17 => (100, target),
+        18 => (100, snailfish),
+        19 => (30, scanners),
+        20 => (100, trench_map),
+        21 => (10, dirac_dice),
+        22 => (420, reboot_steps),
+        23 => (8, amphipods),
+        _ => return None,
+    })
+}
+
+// An input for `day` from `seed`, `size` defaults to that of a real input.
+pub fn generate(day: u8, seed: u64, size: Option<usize>) -> Result<String, String> {
+    let (default, f) = generator(day).ok_or(format!("day{day} has no input generator"))?;
+    Ok(f(&mut Rng::new(seed), size.unwrap_or(default)))
+}
+
+fn join(items: impl Iterator<Item = impl ToString>, sep: &str) -> String {
+    items
+        .map(|i| i.to_string())
+        .collect::<Vec<String>>()
+        .join(sep)
+}
+
+// `size` depths, a random walk that mostly goes down.
+fn depths(rng: &mut Rng, size: usize) -> String {
+    let mut depth = rng.range(100, 200);
+    join(
+        (0..size.max(1)).map(|_| {
+            depth = (depth + rng.range(0, 30)).saturating_sub(10);
+            depth
+        }),
+        "\n",
+    )
+}
+
+// `size` commands, never going up above the surface.
+fn commands(rng: &mut Rng, size: usize) -> String {
+    let mut depth = 0;
+    join(
+        (0..size.max(1)).map(|_| {
+            let x = rng.range(1, 9);
+            match rng.below(3) {
+                0 if depth >= x => {
+                    depth -= x;
+                    format!("up {x}")
+                }
+                1 => {
+                    depth += x;
+                    format!("down {x}")
+                }
+                _ => format!("forward {x}"),
+            }
+        }),
+        "\n",
+    )
+}
+
+// `size` distinct 12 bit numbers, at most 4096.
+fn diagnostics(rng: &mut Rng, size: usize) -> String {
+    let mut numbers = (0..1 << 12).collect::<Vec<u32>>();
+    rng.shuffle(&mut numbers);
+    join(
+        numbers[..size.clamp(1, numbers.len())]
+            .iter()
+            .map(|n| format!("{n:012b}")),
+        "\n",
+    )
+}
+
+// `size` boards, all of 0..100 are drawn so every board wins.
+fn bingo(rng: &mut Rng, size: usize) -> String {
+    let mut numbers = (0..100).collect::<Vec<u32>>();
+    rng.shuffle(&mut numbers);
+    let mut s = join(numbers.iter(), ",");
+    (0..size.max(1)).for_each(|_| {
+        rng.shuffle(&mut numbers);
+        s.push('\n');
+        numbers[..25].chunks(5).for_each(|row| {
+            s.push('\n');
+            s.push_str(&join(row.iter().map(|n| format!("{n:2}")), " "));
+        });
+    });
+    s
+}
+
+// `size` lines, horizontal, vertical or at 45 degrees.
+fn vents(rng: &mut Rng, size: usize) -> String {
+    join(
+        (0..size.max(1)).map(|_| {
+            let (x1, y1) = (rng.below(1000), rng.below(1000));
+            let (dx, dy) = *rng.choose(&[(1, 0), (0, 1), (1, 1), (1, -1)]);
+            let (dx, dy) = if rng.chance(0.5) {
+                (dx, dy)
+            } else {
+                (-dx, -dy)
+            };
+            // The longest line in this direction that stays inside 0..1000.
+            let room = |p: usize, d: i64| match d {
+                1 => 999 - p,
+                -1 => p,
+                _ => usize::MAX,
+            };
+            let len = rng.range(0, room(x1, dx).min(room(y1, dy)).min(300)) as i64;
+            let (x2, y2) = (x1 as i64 + dx * len, y1 as i64 + dy * len);
+            format!("{x1},{y1} -> {x2},{y2}")
+        }),
+        "\n",
+    )
+}
+
+// `size` fish.
+fn lanternfish(rng: &mut Rng, size: usize) -> String {
+    join((0..size.max(1)).map(|_| rng.range(1, 5)), ",")
+}
+
+// `size` crabs.
+fn crabs(rng: &mut Rng, size: usize) -> String {
+    join((0..size.max(1)).map(|_| rng.below(2000)), ",")
+}
+
+// `size` entries, each with its own wiring.
+fn displays(rng: &mut Rng, size: usize) -> String {
+    const DIGITS: [&str; 10] = [
+        "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+    ];
+    join(
+        (0..size.max(1)).map(|_| {
+            let mut wires = "abcdefg".chars().collect::<Vec<char>>();
+            rng.shuffle(&mut wires);
+            let output = (0..4).map(|_| DIGITS[rng.below(10)]).collect::<Vec<&str>>();
+            let mut pattern = |d: &str| {
+                let mut segments = d
+                    .bytes()
+                    .map(|c| wires[(c - b'a') as usize])
+                    .collect::<Vec<char>>();
+                rng.shuffle(&mut segments);
+                segments.into_iter().collect::<String>()
+            };
+            let mut patterns = DIGITS.map(&mut pattern);
+            let output = join(output.into_iter().map(&mut pattern), " ");
+            rng.shuffle(&mut patterns);
+            format!("{} | {output}", patterns.join(" "))
+        }),
+        "\n",
+    )
+}
+
+// A `size` x `size` heightmap.
+fn heightmap(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(2);
+    join(
+        (0..size).map(|_| {
+            (0..size)
+                .map(|_| match rng.chance(0.3) {
+                    true => '9',
+                    false => char::from(b'0' + rng.below(9) as u8),
+                })
+                .collect::<String>()
+        }),
+        "\n",
+    )
+}
+
+// `size` lines, each corrupted or incomplete, with an odd number of incomplete ones.
+fn navigation(rng: &mut Rng, size: usize) -> String {
+    const PAIRS: [(char, char); 4] = [('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')];
+    let mut corrupted = (0..size.max(1))
+        .map(|_| rng.chance(0.5))
+        .collect::<Vec<bool>>();
+    if corrupted.iter().filter(|c| !**c).count() % 2 == 0 {
+        corrupted[0] = !corrupted[0];
+    }
+    join(
+        corrupted.into_iter().map(|corrupt| {
+            let mut line = String::new();
+            let mut open: Vec<usize> = Vec::new();
+            for _ in 0..rng.range(20, 100) {
+                match open.last() {
+                    Some(&i) if rng.chance(0.45) => {
+                        line.push(PAIRS[i].1);
+                        open.pop();
+                    }
+                    _ => {
+                        let i = rng.below(PAIRS.len());
+                        line.push(PAIRS[i].0);
+                        open.push(i);
+                    }
+                }
+            }
+            if open.is_empty() || corrupt {
+                let i = rng.below(PAIRS.len());
+                line.push(PAIRS[i].0);
+                open.push(i);
+            }
+            if corrupt {
+                let wrong = (open[open.len() - 1] + rng.range(1, 3)) % PAIRS.len();
+                line.push(PAIRS[wrong].1);
+                (0..rng.below(10)).for_each(|_| line.push(PAIRS[rng.below(4)].1));
+            }
+            line
+        }),
+        "\n",
+    )
+}
+
+// Whether all octopuses flash at once within `steps` steps.
+fn synchronizes(mut grid: [[u8; 10]; 10], steps: usize) -> bool {
+    (0..steps).any(|_| {
+        let mut flashing = Vec::new();
+        (0..100).for_each(|i| {
+            grid[i / 10][i % 10] += 1;
+            if grid[i / 10][i % 10] == 10 {
+                flashing.push((i % 10, i / 10));
+            }
+        });
+        let mut flashes = 0;
+        while let Some((x, y)) = flashing.pop() {
+            flashes += 1;
+            for (nx, ny) in (x.saturating_sub(1)..=(x + 1).min(9))
+                .flat_map(|nx| (y.saturating_sub(1)..=(y + 1).min(9)).map(move |ny| (nx, ny)))
+            {
+                grid[ny][nx] += 1;
+                if grid[ny][nx] == 10 {
+                    flashing.push((nx, ny));
+                }
+            }
+        }
+        grid.iter_mut()
+            .flatten()
+            .filter(|v| **v > 9)
+            .for_each(|v| *v = 0);
+        flashes == 100
+    })
+}
+
+// The grid is always 10 x 10, `size` is ignored. Grids that do not synchronize within 1000
+// steps are drawn again, the puzzle promises that they do.
+fn octopuses(rng: &mut Rng, _size: usize) -> String {
+    loop {
+        let grid = [[0; 10]; 10].map(|row| row.map(|_: u8| rng.below(10) as u8));
+        if synchronizes(grid, 1000) {
+            return join(
+                grid.iter()
+                    .map(|row| row.map(|v| char::from(b'0' + v)).iter().collect::<String>()),
+                "\n",
+            );
+        }
+    }
+}
+
+// The letters of `n` in base 26, a distinct name for every `n`.
+fn cave_name(mut n: usize, big: bool) -> String {
+    let first = if big { b'A' } else { b'a' };
+    let mut name = vec![first + (n % 26) as u8];
+    while n >= 26 {
+        n /= 26;
+        name.push(first + (n % 26) as u8);
+    }
+    name.iter().rev().map(|c| char::from(*c)).collect()
+}
+
+// `size` caves besides start and end. Big caves are never connected to each other, otherwise
+// there would be endless paths.
+fn caves(rng: &mut Rng, size: usize) -> String {
+    let mut caves = vec!["start".to_string(), "end".to_string()];
+    // Two letters like real caves have, until there are too many of them.
+    let mut n = 26;
+    while caves.len() < size + 2 {
+        let name = cave_name(n, caves.len() % 4 == 0);
+        n += 1;
+        if name != "start" && name != "end" {
+            caves.push(name);
+        }
+    }
+    let big = |i: usize| caves[i].starts_with(|c: char| c.is_ascii_uppercase());
+    let mut edges = Vec::new();
+    let mut seen = HashSet::new();
+    let mut connect = |a: usize, b: usize| {
+        if a != b && !(big(a) && big(b)) && seen.insert((a.min(b), a.max(b))) {
+            edges.push(format!("{}-{}", caves[a], caves[b]));
+        }
+    };
+    // Every cave is linked to an earlier small one so that everything is reachable from start.
+    (2..caves.len()).for_each(|i| {
+        let small = (0..i)
+            .filter(|j| !big(*j) && *j != 1)
+            .collect::<Vec<usize>>();
+        connect(*rng.choose(&small), i);
+    });
+    let small = (2..caves.len())
+        .filter(|i| !big(*i))
+        .collect::<Vec<usize>>();
+    match small.is_empty() {
+        true => connect(0, 1),
+        false => connect(*rng.choose(&small), 1),
+    }
+    (0..size).for_each(|_| {
+        let (a, b) = (rng.range(2, caves.len() - 1), rng.below(caves.len()));
+        connect(a, b)
+    });
+    rng.shuffle(&mut edges);
+    edges.join("\n")
+}
+
+// `size` dots folded ten times into 40 x 6. Dots are made by unfolding random dots of the
+// result, so none of them ends up on a fold.
+fn manual(rng: &mut Rng, size: usize) -> String {
+    let (mut width, mut height) = (40, 6);
+    let mut folds = Vec::new();
+    (0..10).for_each(|i| match i % 2 {
+        0 => {
+            folds.push(('x', width));
+            width = 2 * width + 1;
+        }
+        _ => {
+            folds.push(('y', height));
+            height = 2 * height + 1;
+        }
+    });
+    let mut seen = HashSet::new();
+    let dots = (0..size.max(1))
+        .map(|_| {
+            folds
+                .iter()
+                .fold((rng.below(40), rng.below(6)), |(x, y), (axis, at)| {
+                    match (axis, rng.chance(0.5)) {
+                        ('x', true) => (2 * at - x, y),
+                        ('y', true) => (x, 2 * at - y),
+                        _ => (x, y),
+                    }
+                })
+        })
+        .filter(|d| seen.insert(*d))
+        .map(|(x, y)| format!("{x},{y}"));
+    let folds = folds
+        .iter()
+        .rev()
+        .map(|(axis, at)| format!("fold along {axis}={at}"));
+    format!("{}\n\n{}", join(dots, "\n"), join(folds, "\n"))
+}
+
+// A template of `size` elements, with rules for every pair of ten elements.
+fn polymer(rng: &mut Rng, size: usize) -> String {
+    let elements = "BCFHKNOPSV".chars().collect::<Vec<char>>();
+    let template = (0..size.max(2))
+        .map(|_| *rng.choose(&elements))
+        .collect::<String>();
+    let rules = elements.iter().flat_map(|a| {
+        elements
+            .iter()
+            .map(move |b| [*a, *b].iter().collect::<String>())
+    });
+    let rules = rules.map(|p| format!("{p} -> {}", rng.choose(&elements)));
+    format!("{template}\n\n{}", join(rules, "\n"))
+}
+
+// A `size` x `size` grid of risks.
+fn risks(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(2);
+    join(
+        (0..size).map(|_| {
+            (0..size)
+                .map(|_| char::from(b'1' + rng.below(9) as u8))
+                .collect::<String>()
+        }),
+        "\n",
+    )
+}
+
+fn push(bits: &mut Vec<bool>, value: u64, width: usize) {
+    (0..width)
+        .rev()
+        .for_each(|i| bits.push((value >> i) & 1 == 1))
+}
+
+// Appends the bits of a packet made of `size` packets, including itself, returns its value.
+fn packet(rng: &mut Rng, size: usize, bits: &mut Vec<bool>) -> u64 {
+    // Values are kept well below `u64::MAX` by falling back to smaller operators.
+    const LIMIT: u64 = 1 << 48;
+    push(bits, rng.below(8) as u64, 3);
+    if size <= 1 {
+        let value = rng.next_u64() >> rng.range(40, 63);
+        push(bits, 4, 3);
+        let groups = (64 - value.leading_zeros() as usize).div_ceil(4).max(1);
+        (0..groups).rev().for_each(|g| {
+            push(bits, (g != 0) as u64, 1);
+            push(bits, (value >> (4 * g)) & 0xf, 4);
+        });
+        return value;
+    }
+    let compare = size > 2 && rng.chance(0.3);
+    let count = if compare {
+        2
+    } else {
+        rng.range(1, 4.min(size - 1))
+    };
+    // Splits the other packets between the children, each gets at least one.
+    let mut cuts = (0..count - 1)
+        .map(|_| rng.range(1, size - 2))
+        .collect::<Vec<usize>>();
+    cuts.sort_unstable();
+    cuts.dedup();
+    cuts.insert(0, 0);
+    cuts.push(size - 1);
+    let mut children = Vec::new();
+    let mut values = Vec::new();
+    cuts.windows(2).for_each(|w| {
+        let mut child = Vec::new();
+        values.push(packet(rng, w[1] - w[0], &mut child));
+        children.push(child);
+    });
+    let compare = compare && children.len() == 2;
+    let sum = values.iter().try_fold(0u64, |a, v| a.checked_add(*v));
+    let product = values.iter().try_fold(1u64, |a, v| a.checked_mul(*v));
+    let (kind, value) = match (compare, rng.below(4)) {
+        (true, k) => {
+            let [a, b] = [values[0], values[1]];
+            let (kind, holds) = [(5, a > b), (6, a < b), (7, a == b)][k % 3];
+            (kind, holds as u64)
+        }
+        (false, 1) if product.is_some_and(|p| p < LIMIT) => (1, product.unwrap()),
+        (false, 2) => (2, *values.iter().min().unwrap()),
+        (false, 3) => (3, *values.iter().max().unwrap()),
+        _ if sum.is_some_and(|s| s < LIMIT) => (0, sum.unwrap()),
+        _ => (2, *values.iter().min().unwrap()),
+    };
+    push(bits, kind, 3);
+    let len = children.iter().map(Vec::len).sum::<usize>();
+    if len < 1 << 15 && rng.chance(0.5) {
+        push(bits, 0, 1);
+        push(bits, len as u64, 15);
+    } else {
+        push(bits, 1, 1);
+        push(bits, children.len() as u64, 11);
+    }
+    children.into_iter().for_each(|c| bits.extend(c));
+    value
+}
+
+// A transmission of `size` packets.
+fn transmission(rng: &mut Rng, size: usize) -> String {
+    let mut bits = Vec::new();
+    packet(rng, size.max(1), &mut bits);
+    // Transmissions are whole bytes.
+    bits.resize(bits.len().div_ceil(8) * 8, false);
+    bits.chunks(4)
+        .map(|nibble| {
+            let n = (0..4).fold(0, |a, i| a << 1 | *nibble.get(i).unwrap_or(&false) as u32);
+            char::from_digit(n, 16).unwrap().to_ascii_uppercase()
+        })
+        .collect()
+}
+
+// A target area about `size` away from the launcher.
+fn target(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(10);
+    let x_min = rng.range(size / 2, size);
+    let x_max = x_min + rng.range(5, size / 5 + 5);
+    let y_min = rng.range(size / 2, size);
+    let y_max = y_min.saturating_sub(rng.range(5, size / 5 + 5)).max(1);
+    format!("target area: x={x_min}..{x_max}, y=-{y_min}..-{y_max}")
+}
+
+// A reduced number, `depth` is the number of pairs it is nested in.
+fn snailfish_number(rng: &mut Rng, depth: usize) -> String {
+    let element = |rng: &mut Rng| match depth < 4 && rng.chance(0.6) {
+        true => snailfish_number(rng, depth + 1),
+        false => rng.below(10).to_string(),
+    };
+    let left = element(rng);
+    format!("[{left},{}]", element(rng))
+}
+
+// `size` numbers, all of them reduced.
+fn snailfish(rng: &mut Rng, size: usize) -> String {
+    join((0..size.max(2)).map(|_| snailfish_number(rng, 1)), "\n")
+}
+
+// A random point in the box from `lo` to `hi`.
+fn point_in(rng: &mut Rng, lo: Point, hi: Point) -> Point {
+    let mut coord = |lo: i32, hi: i32| lo + rng.below((hi - lo + 1) as usize) as i32;
+    Point::new(coord(lo.x, hi.x), coord(lo.y, hi.y), coord(lo.z, hi.z))
+}
+
+// `size` scanners, each sees at least 12 of the beacons an earlier one sees.
+fn scanners(rng: &mut Rng, size: usize) -> String {
+    const RANGE: i32 = 1000;
+    let reach = Vector::new(RANGE, RANGE, RANGE);
+    let mut scanners = vec![Point::ORIGIN];
+    let mut beacons = HashSet::new();
+    while scanners.len() < size.max(2) {
+        let parent = *rng.choose(&scanners);
+        // Far along one axis and a little along the others.
+        let axis = rng.below(3);
+        let step = [0, 1, 2].map(|i| {
+            let d = if i == axis {
+                rng.range(1000, 1250)
+            } else {
+                rng.below(400)
+            } as i32;
+            if rng.chance(0.5) {
+                d
+            } else {
+                -d
+            }
+        });
+        let scanner = parent + Vector::new(step[0], step[1], step[2]);
+        // Beacons in the part of space both of them see.
+        let lo = Point::new(
+            parent.x.max(scanner.x),
+            parent.y.max(scanner.y),
+            parent.z.max(scanner.z),
+        ) - reach;
+        let hi = Point::new(
+            parent.x.min(scanner.x),
+            parent.y.min(scanner.y),
+            parent.z.min(scanner.z),
+        ) + reach;
+        let mut shared = 0;
+        while shared < 12 {
+            if beacons.insert(point_in(rng, lo, hi)) {
+                shared += 1;
+            }
+        }
+        scanners.push(scanner);
+    }
+    for s in &scanners {
+        (0..rng.range(8, 14)).for_each(|_| {
+            beacons.insert(point_in(rng, *s - reach, *s + reach));
+        });
+    }
+    let mut beacons = beacons.into_iter().collect::<Vec<Point>>();
+    beacons.sort_unstable();
+    join(
+        scanners.iter().enumerate().map(|(n, s)| {
+            let r = *rng.choose(&Rotation::ALL);
+            let mut seen = beacons
+                .iter()
+                .map(|b| *b - *s)
+                .filter(|v| v.x.abs().max(v.y.abs()).max(v.z.abs()) <= RANGE)
+                .map(|v| v.rotate(r))
+                .map(|v| format!("{},{},{}", v.x, v.y, v.z))
+                .collect::<Vec<String>>();
+            rng.shuffle(&mut seen);
+            format!("--- scanner {n} ---\n{}", seen.join("\n"))
+        }),
+        "\n\n",
+    )
+}
+
+// A `size` x `size` image, if the algorithm lights up dark backgrounds it also turns lit
+// ones dark again.
+fn trench_map(rng: &mut Rng, size: usize) -> String {
+    let mut pixel = |_| if rng.chance(0.5) { '#' } else { '.' };
+    let mut algorithm = (0..512).map(&mut pixel).collect::<Vec<char>>();
+    if algorithm[0] == '#' {
+        algorithm[511] = '.';
+    }
+    let image = (0..size.max(1)).map(|_| (0..size.max(1)).map(&mut pixel).collect::<String>());
+    format!(
+        "{}\n\n{}",
+        algorithm.iter().collect::<String>(),
+        join(image, "\n")
+    )
+}
+
+// Starting spaces on a board of 10, `size` is ignored.
+fn dirac_dice(rng: &mut Rng, _size: usize) -> String {
+    join(
+        (1..=2).map(|n| format!("Player {n} starting position: {}", rng.range(1, 10))),
+        "\n",
+    )
+}
+
+// `size` steps, the first 20 inside the initialization region like in real inputs.
+fn reboot_steps(rng: &mut Rng, size: usize) -> String {
+    let range = |rng: &mut Rng, min: i32, max: i32, len: (usize, usize)| {
+        let lo = min + rng.below((max - min) as usize) as i32;
+        (lo, (lo + rng.range(len.0, len.1) as i32).min(max))
+    };
+    join(
+        (0..size.max(1)).map(|i| {
+            let (min, max, len) = match i < 20 {
+                true => (-50, 50, (0, 50)),
+                false => (-100_000, 100_000, (5_000, 40_000)),
+            };
+            let [(x0, x1), (y0, y1), (z0, z1)] = [0; 3].map(|_| range(rng, min, max, len));
+            let on = if i < 10 || rng.chance(0.5) {
+                "on"
+            } else {
+                "off"
+            };
+            format!("{on} x={x0}..{x1},y={y0}..{y1},z={z0}..{z1}")
+        }),
+        "\n",
+    )
+}
+
+// Two of each amphipod shuffled over the rooms, `size` is ignored. Shuffles again until the
+// unfolded burrow can be organized, some can't.
+fn amphipods(rng: &mut Rng, _size: usize) -> String {
+    let mut amphipods = *b"AABBCCDD";
+    loop {
+        rng.shuffle(&mut amphipods);
+        let [top, bottom] = [0, 1].map(|d| join((0..4).map(|r| amphipods[2 * r + d] as char), "#"));
+        let s = format!("#############\n#...........#\n###{top}###\n  #{bottom}#\n  #########");
+        let burrow = s.parse::<Burrow<2>>().unwrap();
+        if burrow.unfold().organize().is_some() {
+            return s;
+        }
+    }
+}
+
+#[test]
+fn test_generate() {
+    use crate::solver;
+
+    assert_eq!(Rng::new(1).next_u64(), Rng::new(1).next_u64());
+    assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64());
+    let mut rng = Rng::new(3);
+    assert!((0..1000).all(|_| (2..=5).contains(&rng.range(2, 5))));
+
+    // Days fresh from `new` have no generator yet.
+    for day in solver::DAYS.iter().filter(|d| generator(d.day).is_some()) {
+        for seed in 0..5 {
+            let input = generate(day.day, seed, Some(12)).unwrap();
+            assert_eq!(generate(day.day, seed, Some(12)).unwrap(), input);
+            let solution = (day.solve)(&input, None, &mut |_| {})
+                .unwrap_or_else(|e| panic!("day{} seed {seed}: {}", day.day, e.diagnostic(&input)));
+            assert_eq!(solution.parts.len(), 2);
+        }
+    }
+    // More caves than there are two letter names.
+    let input = generate(12, 0, Some(1000)).unwrap();
+    let caves = input.split(['\n', '-']).collect::<HashSet<&str>>();
+    assert_eq!(caves.len(), 1002);
+    assert_eq!(
+        generate(25, 0, None).unwrap_err(),
+        "day25 has no input generator"
+    );
+}
@@ -0,0 +1,286 @@
+// Points, vectors and cuboids on an integer 3D lattice, and the 24 rotations that map the
+// axes onto each other.
+use std::{
+    ops::{Add, Neg, Sub},
+    str::FromStr,
+};
+
+use crate::error::{self, AocError};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+// The difference of two points.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vector {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0, z: 0 };
+
+    pub fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn manhattan(self, other: Point) -> i32 {
+        (self - other).manhattan()
+    }
+
+    pub fn rotate(self, r: Rotation) -> Self {
+        let [x, y, z] = r.apply([self.x, self.y, self.z]);
+        Self { x, y, z }
+    }
+}
+
+impl Vector {
+    pub fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn manhattan(self) -> i32 {
+        self.x.abs() + self.y.abs() + self.z.abs()
+    }
+
+    pub fn rotate(self, r: Rotation) -> Self {
+        let [x, y, z] = r.apply([self.x, self.y, self.z]);
+        Self { x, y, z }
+    }
+
+    // The sorted absolute coordinates, the same for a vector and all of its rotations.
+    pub fn fingerprint(self) -> [i32; 3] {
+        let mut f = [self.x.abs(), self.y.abs(), self.z.abs()];
+        f.sort_unstable();
+        f
+    }
+}
+
+impl Sub for Point {
+    type Output = Vector;
+
+    fn sub(self, other: Point) -> Vector {
+        Vector::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl Add<Vector> for Point {
+    type Output = Point;
+
+    fn add(self, v: Vector) -> Point {
+        Point::new(self.x + v.x, self.y + v.y, self.z + v.z)
+    }
+}
+
+impl Sub<Vector> for Point {
+    type Output = Point;
+
+    fn sub(self, v: Vector) -> Point {
+        self + -v
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, v: Vector) -> Vector {
+        Vector::new(self.x + v.x, self.y + v.y, self.z + v.z)
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Vector::new(-self.x, -self.y, -self.z)
+    }
+}
+
+// `x,y,z`, columns of errors are relative to `s`.
+impl FromStr for Point {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, AocError> {
+        let coords = s
+            .split(',')
+            .map(|c| error::number(s, c))
+            .collect::<Result<Vec<i32>, AocError>>()?;
+        match coords[..] {
+            [x, y, z] => Ok(Point::new(x, y, z)),
+            _ => Err(AocError::new(1, format!("expected 'x,y,z', found '{s}'"))),
+        }
+    }
+}
+
+// All points from `min` to `max` on every axis, like `day17::Bucket` in 3D.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cuboid {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Cuboid {
+    pub fn new(min: Point, max: Point) -> Self {
+        Self { min, max }
+    }
+
+    pub fn volume(&self) -> i64 {
+        // Widened first, a side from i32::MIN to i32::MAX is longer than any i32.
+        let side = |min: i32, max: i32| max as i64 - min as i64 + 1;
+        side(self.min.x, self.max.x) * side(self.min.y, self.max.y) * side(self.min.z, self.max.z)
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x)
+            && (self.min.y..=self.max.y).contains(&p.y)
+            && (self.min.z..=self.max.z).contains(&p.z)
+    }
+
+    // The points in both, None if there are none.
+    pub fn intersection(&self, other: &Cuboid) -> Option<Cuboid> {
+        let min = Point::new(
+            self.min.x.max(other.min.x),
+            self.min.y.max(other.min.y),
+            self.min.z.max(other.min.z),
+        );
+        let max = Point::new(
+            self.max.x.min(other.max.x),
+            self.max.y.min(other.max.y),
+            self.max.z.min(other.max.z),
+        );
+        (min.x <= max.x && min.y <= max.y && min.z <= max.z).then_some(Cuboid { min, max })
+    }
+}
+
+// Coordinate `i` of the result is coordinate `axes[i]` of the input times `signs[i]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rotation {
+    axes: [usize; 3],
+    signs: [i32; 3],
+}
+
+impl Rotation {
+    pub const IDENTITY: Rotation = Rotation {
+        axes: [0, 1, 2],
+        signs: [1, 1, 1],
+    };
+
+    // All 24 orientations of a cube, starting with the identity. Mirror images are left out,
+    // they would turn a right-handed coordinate system into a left-handed one.
+    pub const ALL: [Rotation; 24] = Rotation::all();
+
+    const fn all() -> [Rotation; 24] {
+        const PERMUTATIONS: [[usize; 3]; 6] = [
+            [0, 1, 2],
+            [1, 2, 0],
+            [2, 0, 1],
+            [0, 2, 1],
+            [2, 1, 0],
+            [1, 0, 2],
+        ];
+        // Bit `b` of `bits` set flips axis `b`.
+        const fn sign(bits: usize, b: usize) -> i32 {
+            if bits >> b & 1 == 0 {
+                1
+            } else {
+                -1
+            }
+        }
+        let mut all = [Rotation::IDENTITY; 24];
+        let mut n = 0;
+        let mut i = 0;
+        while i < PERMUTATIONS.len() {
+            // The first three permutations are even.
+            let parity = if i < 3 { 1 } else { -1 };
+            let mut bits = 0;
+            while bits < 8 {
+                let signs = [sign(bits, 0), sign(bits, 1), sign(bits, 2)];
+                if signs[0] * signs[1] * signs[2] == parity {
+                    all[n] = Rotation {
+                        axes: PERMUTATIONS[i],
+                        signs,
+                    };
+                    n += 1;
+                }
+                bits += 1;
+            }
+            i += 1;
+        }
+        all
+    }
+
+    pub fn apply(self, v: [i32; 3]) -> [i32; 3] {
+        [0, 1, 2].map(|i| v[self.axes[i]] * self.signs[i])
+    }
+
+    // The rotation undoing this one.
+    pub fn inverse(self) -> Rotation {
+        let mut inverse = self;
+        (0..3).for_each(|i| {
+            inverse.axes[self.axes[i]] = i;
+            inverse.signs[self.axes[i]] = self.signs[i];
+        });
+        inverse
+    }
+}
+
+#[test]
+fn test_geometry() {
+    let (a, b) = (Point::new(1, -2, 3), Point::new(-4, 5, 6));
+    assert_eq!(b - a, Vector::new(-5, 7, 3));
+    assert_eq!(a + (b - a), b);
+    assert_eq!(b - (b - a), a);
+    assert_eq!((a.manhattan(b), b.manhattan(a)), (15, 15));
+    assert_eq!(Vector::new(-5, 7, 3).fingerprint(), [3, 5, 7]);
+    assert_eq!("-618,-824,-621".parse(), Ok(Point::new(-618, -824, -621)));
+    assert_eq!(
+        "1,2".parse::<Point>(),
+        Err(AocError::new(1, "expected 'x,y,z', found '1,2'"))
+    );
+    assert_eq!(
+        "1,y,2".parse::<Point>(),
+        Err(AocError::new(3, "expected a number, found 'y'"))
+    );
+
+    let c = Cuboid::new(Point::new(10, 10, 10), Point::new(12, 12, 12));
+    let d = Cuboid::new(Point::new(11, 11, 11), Point::new(13, 13, 13));
+    assert_eq!(c.volume(), 27);
+    assert!(c.contains(Point::new(10, 12, 11)) && !c.contains(Point::new(9, 10, 10)));
+    assert_eq!(
+        c.intersection(&d),
+        Some(Cuboid::new(Point::new(11, 11, 11), Point::new(12, 12, 12)))
+    );
+    assert_eq!(c.intersection(&d), d.intersection(&c));
+    let apart = Cuboid::new(Point::new(13, 0, 0), Point::new(20, 20, 20));
+    assert_eq!(c.intersection(&apart), None);
+    let flat = Cuboid::new(Point::new(12, 12, 12), Point::new(12, 12, 12));
+    assert_eq!(c.intersection(&flat).map(|i| i.volume()), Some(1));
+    let long = Cuboid::new(Point::new(i32::MIN, 0, 0), Point::new(i32::MAX, 1, 0));
+    assert_eq!(long.volume(), 2 << 32);
+
+    let rotations = Rotation::ALL;
+    assert_eq!(rotations[0], Rotation::IDENTITY);
+    let images = rotations
+        .iter()
+        .map(|r| a.rotate(*r))
+        .collect::<std::collections::HashSet<Point>>();
+    assert_eq!(images.len(), 24);
+    // Rotations keep lengths and handedness: x times y is still z.
+    let v = Vector::new(1, 2, 3);
+    for r in rotations {
+        assert_eq!(v.rotate(r).fingerprint(), v.fingerprint());
+        let [x, y, z] = [[1, 0, 0], [0, 1, 0], [0, 0, 1]].map(|a| r.apply(a));
+        let cross = [
+            x[1] * y[2] - x[2] * y[1],
+            x[2] * y[0] - x[0] * y[2],
+            x[0] * y[1] - x[1] * y[0],
+        ];
+        assert_eq!(cross, z);
+        assert_eq!(a.rotate(r).rotate(r.inverse()), a);
+    }
+}
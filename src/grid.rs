@@ -0,0 +1,269 @@
+use std::ops::{Index, IndexMut};
+
+use crate::error::{self, AocError};
+
+pub type Pos = (usize, usize);
+
+const NEIGHBOURS4: [(i64, i64); 4] = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+const NEIGHBOURS8: [(i64, i64); 8] = [
+    (0, -1),
+    (0, 1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (1, 1),
+    (-1, -1),
+    (1, -1),
+];
+
+// Positions next to `(x, y)` inside a `width` x `height` area.
+fn neighbours(
+    (x, y): Pos,
+    (width, height): (usize, usize),
+    offsets: &'static [(i64, i64)],
+) -> impl Iterator<Item = Pos> {
+    offsets.iter().filter_map(move |(dx, dy)| {
+        let nx = usize::try_from(x as i64 + dx).ok()?;
+        let ny = usize::try_from(y as i64 + dy).ok()?;
+        (nx < width && ny < height).then_some((nx, ny))
+    })
+}
+
+// A rectangular grid stored row by row, positions are `(x, y)` with `y` growing downwards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Pos) -> T) -> Self {
+        Self {
+            width,
+            height,
+            cells: (0..height)
+                .flat_map(|y| (0..width).map(move |x| (x, y)))
+                .map(&mut f)
+                .collect(),
+        }
+    }
+
+    // All rows must be as long as the first one.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, AocError> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+        for (y, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(AocError::new(
+                    row.len().min(width) + 1,
+                    format!("expected {width} cells, found {}", row.len()),
+                )
+                .on_line(y + 1));
+            }
+            cells.extend(row);
+        }
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    // One line per row, `f` parses a character at a 1-based column.
+    pub fn parse(
+        s: &str,
+        f: impl Fn(char, usize) -> Result<T, AocError>,
+    ) -> Result<Self, AocError> {
+        Self::from_rows(error::lines(s, |l| {
+            l.chars().enumerate().map(|(i, c)| f(c, i + 1)).collect()
+        })?)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (x, y): Pos) -> Option<&T> {
+        (x < self.width && y < self.height).then(|| &self.cells[y * self.width + x])
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    // Cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    // Up, down, left and right of `p`, if inside the grid.
+    pub fn neighbours4(&self, p: Pos) -> impl Iterator<Item = Pos> {
+        neighbours(p, (self.width, self.height), &NEIGHBOURS4)
+    }
+
+    // Like `neighbours4`, including the diagonals.
+    pub fn neighbours8(&self, p: Pos) -> impl Iterator<Item = Pos> {
+        neighbours(p, (self.width, self.height), &NEIGHBOURS8)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1)).take(self.height)
+    }
+
+    pub fn cols(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| (0..self.height).map(move |y| &self[(x, y)]))
+    }
+
+    // The grid repeated `across` times to the right and `down` times downwards.
+    pub fn tiled(&self, across: usize, down: usize) -> Tiled<'_, T> {
+        Tiled {
+            grid: self,
+            tiles: (across, down),
+        }
+    }
+
+    // The grid repeated endlessly in all directions.
+    pub fn wrapping(&self) -> Wrapping<'_, T> {
+        Wrapping { grid: self }
+    }
+
+    // One line per row, `f` draws a cell.
+    pub fn render(&self, f: impl Fn(&T) -> char) -> String {
+        self.rows()
+            .map(|row| row.iter().map(&f).collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+impl Grid<u8> {
+    // A grid of single digits, such as a heightmap.
+    pub fn digits(s: &str) -> Result<Self, AocError> {
+        Self::parse(s, error::digit)
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): Pos) -> &T {
+        assert!(x < self.width, "x {x} out of bounds, width {}", self.width);
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, (x, y): Pos) -> &mut T {
+        assert!(x < self.width, "x {x} out of bounds, width {}", self.width);
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Tiled<'a, T> {
+    grid: &'a Grid<T>,
+    tiles: (usize, usize),
+}
+
+impl<'a, T> Tiled<'a, T> {
+    pub fn width(&self) -> usize {
+        self.grid.width * self.tiles.0
+    }
+
+    pub fn height(&self) -> usize {
+        self.grid.height * self.tiles.1
+    }
+
+    // The cell at `(x, y)` and the tile it is in, tile `(0, 0)` is the grid itself.
+    pub fn get(&self, (x, y): Pos) -> Option<(Pos, &'a T)> {
+        if x >= self.width() || y >= self.height() {
+            return None;
+        }
+        let (w, h) = (self.grid.width, self.grid.height);
+        Some(((x / w, y / h), &self.grid[(x % w, y % h)]))
+    }
+
+    pub fn neighbours4(&self, p: Pos) -> impl Iterator<Item = Pos> {
+        neighbours(p, (self.width(), self.height()), &NEIGHBOURS4)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Wrapping<'a, T> {
+    grid: &'a Grid<T>,
+}
+
+impl<'a, T> Wrapping<'a, T> {
+    // The cell at `(x, y)`, which may be anywhere, even left of or above the grid.
+    pub fn get(&self, (x, y): (i64, i64)) -> &'a T {
+        let (w, h) = (self.grid.width as i64, self.grid.height as i64);
+        &self.grid[(x.rem_euclid(w) as usize, y.rem_euclid(h) as usize)]
+    }
+}
+
+#[test]
+fn test_grid() {
+    let g = Grid::digits("123\n456").unwrap();
+    assert_eq!((g.width(), g.height()), (3, 2));
+    assert_eq!(
+        (g[(2, 0)], g.get((1, 1)), g.get((3, 0))),
+        (3, Some(&5), None)
+    );
+    assert_eq!(
+        g.neighbours4((0, 0)).collect::<Vec<Pos>>(),
+        [(0, 1), (1, 0)]
+    );
+    assert_eq!(g.neighbours8((2, 1)).count(), 3);
+    assert_eq!(g.neighbours8((1, 0)).count(), 5);
+    assert_eq!(g.rows().collect::<Vec<&[u8]>>(), [[1, 2, 3], [4, 5, 6]]);
+    assert_eq!(
+        g.cols()
+            .map(|c| c.copied().collect::<Vec<u8>>())
+            .collect::<Vec<Vec<u8>>>(),
+        [[1, 4], [2, 5], [3, 6]]
+    );
+    assert_eq!(g.iter().nth(4), Some(((1, 1), &5)));
+    assert_eq!(g.render(|d| char::from(b'0' + d)), "123\n456".to_string());
+    assert_eq!(Grid::from_fn(3, 2, |(x, y)| (1 + x + 3 * y) as u8), g);
+
+    let tiled = g.tiled(2, 3);
+    assert_eq!((tiled.width(), tiled.height()), (6, 6));
+    assert_eq!(tiled.get((4, 5)), Some(((1, 2), &5)));
+    assert_eq!(tiled.get((6, 0)), None);
+    assert_eq!(tiled.neighbours4((5, 5)).count(), 2);
+    assert_eq!(g.wrapping().get((-1, 2)), &3);
+    assert_eq!(g.wrapping().get((4, -3)), &5);
+
+    assert_eq!(
+        Grid::digits("123\n45").unwrap_err(),
+        AocError::new(3, "expected 3 cells, found 2").on_line(2)
+    );
+    assert_eq!(
+        Grid::digits("12\n3x").unwrap_err(),
+        AocError::new(2, "expected a digit, found 'x'").on_line(2)
+    );
+    let empty = Grid::<u8>::digits("").unwrap();
+    assert_eq!((empty.width(), empty.rows().count()), (0, 0));
+}
@@ -0,0 +1,134 @@
+use std::{
+    env, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+pub const INPUT_DIR: &str = "src/inputs";
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+const BOM: char = '\u{feff}';
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+}
+
+impl From<&str> for Source {
+    fn from(s: &str) -> Self {
+        match s {
+            "-" => Source::Stdin,
+            _ => Source::File(PathBuf::from(s)),
+        }
+    }
+}
+
+pub fn file_name(day: u8) -> String {
+    format!("aoc_{day}.input")
+}
+
+pub fn path(dir: &Path, day: u8) -> PathBuf {
+    dir.join(file_name(day))
+}
+
+pub fn sample_path(dir: &Path, day: u8) -> PathBuf {
+    dir.join(format!("aoc_{day}_sample.input"))
+}
+
+// Puzzle inputs live in $AOC_INPUT_DIR if set, `src/inputs` otherwise.
+pub fn dir() -> PathBuf {
+    env::var_os(INPUT_DIR_VAR).map_or(PathBuf::from(INPUT_DIR), PathBuf::from)
+}
+
+pub fn default_source(day: u8) -> Source {
+    Source::File(path(&dir(), day))
+}
+
+// Drops a byte order mark, trailing whitespace and trailing blank lines, and turns CRLF and CR
+// line endings into LF. The result has no final line break.
+pub fn normalize(s: &str) -> String {
+    s.strip_prefix(BOM)
+        .unwrap_or(s)
+        .replace("\r\n", "\n")
+        .replace('\r', "\n")
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<&str>>()
+        .join("\n")
+        .trim_end()
+        .to_string()
+}
+
+// Splits a normalized input at runs of blank lines, each paragraph with the 1-based line it
+// starts on.
+pub fn paragraphs(s: &str) -> Vec<(usize, &str)> {
+    let mut spans: Vec<(usize, usize, usize)> = Vec::new();
+    let mut start = 0;
+    for (n, line) in s.split('\n').enumerate() {
+        let end = start + line.len();
+        if !line.trim().is_empty() {
+            match spans.last_mut() {
+                Some((_, _, e)) if *e + 1 == start => *e = end,
+                _ => spans.push((n + 1, start, end)),
+            }
+        }
+        start = end + 1;
+    }
+    spans.into_iter().map(|(n, a, b)| (n, &s[a..b])).collect()
+}
+
+// Inputs are normalized before any solver sees them.
+pub fn load(source: &Source) -> Result<String, String> {
+    match source {
+        Source::File(path) => fs::read_to_string(path)
+            .map_err(|e| format!("cannot read input {}: {e}", path.display())),
+        Source::Stdin => {
+            let mut s = String::new();
+            io::stdin()
+                .read_to_string(&mut s)
+                .map_err(|e| format!("cannot read input from stdin: {e}"))?;
+            Ok(s)
+        }
+    }
+    .map(|s| normalize(&s))
+}
+
+#[test]
+fn test_load() {
+    assert_eq!(Source::from("-"), Source::Stdin);
+    assert_eq!(
+        Source::from("foo.txt"),
+        Source::File(PathBuf::from("foo.txt"))
+    );
+    assert!(load(&Source::File(path(Path::new(INPUT_DIR), 1))).is_ok());
+    let e = load(&Source::from("src/inputs/nope.input")).unwrap_err();
+    assert!(e.starts_with("cannot read input src/inputs/nope.input: "));
+}
+
+#[test]
+fn test_normalize() {
+    assert_eq!(normalize("\u{feff}1 \r\n2\t\r\n\r\n"), "1\n2");
+    assert_eq!(normalize("a\rb\n\n\nc\n"), "a\nb\n\n\nc");
+    assert_eq!(normalize(" \n"), "");
+    assert_eq!(
+        paragraphs("1\n2\n\n\n3\n\n4\n5"),
+        [(1, "1\n2"), (5, "3"), (7, "4\n5")]
+    );
+    assert_eq!(paragraphs(""), []);
+    assert_eq!(paragraphs("\n1"), [(2, "1")]);
+
+    // Solvers that used to reject such files.
+    let solve = |day, input: &str| {
+        let day = crate::solver::find(crate::solver::YEAR, day).unwrap();
+        (day.solve)(&normalize(input), Some(1), &mut |_| {}).map(|s| s.parts[0].answer.clone())
+    };
+    assert_eq!(
+        solve(16, "\u{feff}8A004A801A8002F478\r\n"),
+        Ok("16".to_string())
+    );
+    assert_eq!(
+        solve(9, "2199943210\r\n3987894921\r\n"),
+        Ok("11".to_string())
+    );
+}
@@ -0,0 +1,45 @@
+#![cfg_attr(feature = "nightly", feature(iter_map_windows, cmp_minmax))]
+
+pub mod answers;
+pub mod bench;
+pub mod cancel;
+pub mod cli;
+pub mod compat;
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day2;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod error;
+pub mod gen;
+pub mod geometry;
+pub mod grid;
+pub mod input;
+pub mod mem;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod search;
+pub mod solver;
+// Compiled so that days created by `new` start from a template that builds.
+#[cfg(test)]
+#[allow(dead_code)]
+mod template;
@@ -1,70 +1,234 @@
-#![feature(iter_map_windows)]
-#![feature(iter_next_chunk)]
-#![feature(cmp_minmax)]
+use std::{env, fs, path::Path, process, time::Instant};
 
-use rayon::prelude::*;
-use std::{env, time::Instant};
+use aoc2021::{
+    answers::{self, Answers},
+    bench,
+    cli::{self, Args, Command, Format},
+    gen, input, mem, report,
+    report::DayResult,
+    runner::{self, Job},
+    scaffold,
+    solver::{self, Day, YEAR},
+};
 
-mod day1;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
+// Only counts allocations if enabled with --mem.
+#[global_allocator]
+static GLOBAL: mem::Counting = mem::Counting;
 
-fn main() {
-    let days = [
-        (12, day12::run as fn() -> (String, String)),
-        (5, day5::run),
-        (7, day7::run),
-        (9, day9::run),
-        (8, day8::run),
-        (1, day1::run),
-        (2, day2::run),
-        (3, day3::run),
-        (4, day4::run),
-        (6, day6::run),
-        (10, day10::run),
-        (11, day11::run),
-        (13, day13::run),
-        (14, day14::run),
-        (15, day15::run),
-        (16, day16::run),
-        (17, day17::run),
-        (18, day18::run),
-    ];
+fn lookup(selected: &[u8]) -> Vec<&'static Day> {
+    let missing = selected
+        .iter()
+        .filter(|d| solver::find(YEAR, **d).is_none())
+        .collect::<Vec<&u8>>();
+    if !missing.is_empty() {
+        missing
+            .iter()
+            .for_each(|d| eprintln!("day{d} is not implemented"));
+        process::exit(1);
+    }
+    selected
+        .iter()
+        .filter_map(|d| solver::find(YEAR, *d))
+        .collect()
+}
+
+fn load_answers(optional: bool) -> Answers {
+    let dir = input::dir();
+    if optional && !answers::path(&dir).exists() {
+        return Answers::default();
+    }
+    answers::load(&dir).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    })
+}
+
+fn load_selected(args: &Args, days: &[u8], answers: &Answers) -> Vec<Job> {
+    runner::load(
+        lookup(days),
+        |day| {
+            args.input
+                .clone()
+                .unwrap_or_else(|| input::default_source(day))
+        },
+        answers,
+    )
+}
+
+fn thread_pool(args: &Args) -> rayon::ThreadPool {
+    runner::thread_pool(args.jobs).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    })
+}
+
+fn exit_on_failure(days: &[DayResult]) {
+    days.iter()
+        .filter_map(|d| d.diagnostic.as_ref().map(|diag| (d, diag)))
+        .for_each(|(d, diag)| match &d.input {
+            Some(input) => eprintln!("day{} ({input}): malformed input, {diag}", d.day),
+            None => eprintln!("day{}: malformed input, {diag}", d.day),
+        });
+    let failed = days.iter().filter(|d| d.failed()).count();
+    if failed != 0 {
+        eprintln!("{failed} day(s) failed");
+        process::exit(failed.min(255) as i32);
+    }
+}
+
+fn run(args: &Args, jobs: &[Job]) {
+    let pool = thread_pool(args);
+    let threads = pool.current_num_threads();
     let now = Instant::now();
-    let day = env::args()
-        .nth(1)
-        .unwrap_or("0".to_string())
-        .parse::<usize>()
-        .unwrap_or(0);
-    match day {
-        1..=25 => {
-            let (p1, p2) = days[day - 1].1();
-            println!("day{day} p1: {p1}\nday{day} p2: {p2}");
+    let days = runner::solve_all(&pool, jobs, args.part, args.timeout);
+    let wall = now.elapsed();
+    println!(
+        "{}",
+        report::render(args.format, &runner::records(&days), threads)
+    );
+    if args.format == Format::Text {
+        println!("\n{}", report::summary(&days, wall, threads));
+    }
+    exit_on_failure(&days);
+}
+
+fn check(args: &Args, jobs: &[Job]) {
+    let pool = thread_pool(args);
+    let days = runner::solve_all(&pool, jobs, args.part, args.timeout);
+    let threads = pool.current_num_threads();
+    println!(
+        "{}",
+        report::render(args.format, &runner::records(&days), threads)
+    );
+    exit_on_failure(&days);
+}
+
+// Days are measured one at a time so they do not compete for cores. A baseline is only saved
+// if every day could be measured, it would lack the failed ones otherwise.
+fn bench(args: &Args, jobs: &[Job]) {
+    let opts = &args.bench;
+    let baseline = opts
+        .baseline
+        .as_ref()
+        .map_or(Ok(bench::Baseline::new()), |p| bench::load_baseline(p))
+        .unwrap_or_else(|e| {
+            eprintln!("{e}");
+            process::exit(1);
+        });
+    let mut failed = 0;
+    let measurements = jobs
+        .iter()
+        .flat_map(|job| {
+            let measured = job.input.as_ref().map_err(String::clone).and_then(|input| {
+                runner::catch_solver(|| {
+                    bench::measure(job.day, input, args.part, opts.warmup, opts.iterations)
+                })
+                .map_err(|payload| runner::panic_message(payload, None))?
+                .map_err(|e| format!("malformed input, {}", e.diagnostic(input)))
+            });
+            measured.unwrap_or_else(|e| {
+                eprintln!("day{}: {e}", job.day.day);
+                failed += 1;
+                Vec::new()
+            })
+        })
+        .collect::<Vec<bench::Measurement>>();
+    println!("{}", bench::table(&measurements, &baseline, opts.threshold));
+    if failed != 0 {
+        if opts.save_baseline.is_some() {
+            eprintln!("not saving the baseline");
         }
-        _ => days.par_iter().for_each(|day| {
-            let now = Instant::now();
-            let (p1, p2) = day.1();
-            println!(
-                "day{day_n} p1: {p1}\nday{day_n} p2: {p2}\nday{day_n} execution time: {:?}",
-                now.elapsed(),
-                day_n = day.0
-            );
-        }),
+        eprintln!("{failed} day(s) failed");
+        process::exit(failed.min(255));
+    }
+    if let Some(path) = &opts.save_baseline {
+        bench::save_baseline(path, &measurements).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            process::exit(1);
+        });
+    }
+    let regressions = bench::regressions(&measurements, &baseline, opts.threshold);
+    if regressions != 0 {
+        eprintln!(
+            "{regressions} regression(s) beyond {}% of the baseline",
+            opts.threshold * 100.0
+        );
+        process::exit(regressions.min(255) as i32);
+    }
+}
+
+fn list() {
+    (1..=25).for_each(|d| match solver::find(YEAR, d) {
+        Some(_) => println!("day{d}: implemented"),
+        None => println!("day{d}: not implemented"),
+    });
+}
+
+// Run from the repository root, everything is checked before any file is written.
+fn new(day: u8) {
+    let fail = |e: String| -> ! {
+        eprintln!("{e}");
+        process::exit(1);
+    };
+    let path = format!("src/day{day}.rs");
+    if Path::new(&path).exists() {
+        fail(format!("{path} already exists"));
+    }
+    let read =
+        |path: &str| fs::read_to_string(path).unwrap_or_else(|e| fail(format!("{path}: {e}")));
+    let lib = scaffold::register_module(&read("src/lib.rs"), day).unwrap_or_else(|e| fail(e));
+    let solver = scaffold::register_day(&read("src/solver.rs"), day).unwrap_or_else(|e| fail(e));
+    let dir = Path::new(input::INPUT_DIR);
+    let inputs = [input::path(dir, day), input::sample_path(dir, day)];
+    let write = |path: &Path, contents: &str| {
+        fs::write(path, contents).unwrap_or_else(|e| fail(format!("{}: {e}", path.display())))
+    };
+    write(
+        Path::new(&path),
+        &scaffold::day_source(scaffold::TEMPLATE, day),
+    );
+    inputs
+        .iter()
+        .filter(|p| !p.exists())
+        .for_each(|p| write(p, ""));
+    write(Path::new("src/lib.rs"), &lib);
+    write(Path::new("src/solver.rs"), &solver);
+    println!(
+        "created {path} and empty {} and {}, registered day{day}",
+        inputs[0].display(),
+        inputs[1].display()
+    );
+}
+
+fn generate(day: u8, args: &Args) {
+    match gen::generate(day, args.gen.seed, args.gen.size) {
+        Ok(input) => println!("{input}"),
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn main() {
+    let args = cli::parse(env::args().skip(1)).unwrap_or_else(|e| {
+        eprintln!("{e}\n\n{}", cli::USAGE);
+        process::exit(2);
+    });
+    runner::install_panic_hook();
+    if args.mem {
+        mem::enable();
+    }
+    match &args.command {
+        Command::Run(days) => run(&args, &load_selected(&args, days, &load_answers(true))),
+        Command::Check(days) => check(
+            &args,
+            &runner::load_listed(lookup(days), &load_answers(false)),
+        ),
+        Command::Bench(days) => bench(&args, &load_selected(&args, days, &Answers::default())),
+        Command::List => list(),
+        Command::New(day) => new(*day),
+        Command::Gen(day) => generate(*day, &args),
+        Command::Help => println!("{}", cli::USAGE),
     }
-    println!("total execution time: {:?}", now.elapsed());
 }
@@ -0,0 +1,210 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    fmt,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+// Heap usage of a thread while running some code, see `measure`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Usage {
+    pub allocations: u64,
+    // Total size of all allocations, memory that is freed again is not subtracted.
+    pub bytes: u64,
+    // Largest amount of memory allocated and not yet freed at any one time.
+    pub peak: u64,
+    // Memory allocated and not yet freed at the end, negative if more was freed.
+    pub live: i64,
+}
+
+impl Usage {
+    // The usage of two phases that ran one after the other. Memory still held after the first
+    // phase, a parsed input for example, counts towards the peak of the second.
+    pub fn then(self, other: Usage) -> Usage {
+        Usage {
+            allocations: self.allocations + other.allocations,
+            bytes: self.bytes + other.bytes,
+            peak: self.peak.max((self.live + other.peak as i64).max(0) as u64),
+            live: self.live + other.live,
+        }
+    }
+}
+
+// A byte count in B, KiB, MiB or GiB.
+pub struct Bytes(pub u64);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let units = ["B", "KiB", "MiB", "GiB"];
+        let mut size = self.0 as f64;
+        let mut unit = 0;
+        while size >= 1024.0 && unit + 1 < units.len() {
+            size /= 1024.0;
+            unit += 1;
+        }
+        match unit {
+            0 => write!(f, "{}B", self.0),
+            _ => write!(f, "{size:.1}{}", units[unit]),
+        }
+    }
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Clone, Copy)]
+struct Counters {
+    allocations: u64,
+    bytes: u64,
+    // Signed, memory may be freed by another thread than the one that allocated it.
+    current: i64,
+    peak: i64,
+}
+
+const ZERO: Counters = Counters {
+    allocations: 0,
+    bytes: 0,
+    current: 0,
+    peak: 0,
+};
+
+thread_local! {
+    // Allocations are counted per thread so that days solved in parallel do not mix.
+    static COUNTERS: Cell<Counters> = const { Cell::new(ZERO) };
+}
+
+// Wraps the system allocator, counting allocations once `enable` was called. Installed by the
+// binary with `#[global_allocator]`.
+pub struct Counting;
+
+fn record(size: usize, freed: usize) {
+    // Ignore allocations while the thread is torn down.
+    let _ = COUNTERS.try_with(|c| {
+        let mut counters = c.get();
+        if size != 0 {
+            counters.allocations += 1;
+            counters.bytes += size as u64;
+        }
+        counters.current += size as i64 - freed as i64;
+        counters.peak = counters.peak.max(counters.current);
+        c.set(counters);
+    });
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() && ENABLED.load(Ordering::Relaxed) {
+            record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() && ENABLED.load(Ordering::Relaxed) {
+            record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        if ENABLED.load(Ordering::Relaxed) {
+            record(0, layout.size());
+        }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() && ENABLED.load(Ordering::Relaxed) {
+            record(new_size, layout.size());
+        }
+        new
+    }
+}
+
+// Starts counting, only has an effect if `Counting` is the global allocator.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+// Runs `f` and returns the heap usage of the current thread meanwhile, None if counting is
+// not enabled. Nested measurements are not supported.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Usage>) {
+    if !enabled() {
+        return (f(), None);
+    }
+    COUNTERS.set(ZERO);
+    let result = f();
+    let counters = COUNTERS.get();
+    (
+        result,
+        Some(Usage {
+            allocations: counters.allocations,
+            bytes: counters.bytes,
+            peak: counters.peak.max(0) as u64,
+            live: counters.current,
+        }),
+    )
+}
+
+#[cfg(test)]
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+#[test]
+fn test_measure() {
+    enable();
+    let (v, usage) = measure(|| {
+        let a = std::hint::black_box(vec![0u8; 1000]);
+        let b = std::hint::black_box(vec![0u8; 500]);
+        drop(a);
+        let c = std::hint::black_box(vec![0u8; 200]);
+        b.len() + c.len()
+    });
+    assert_eq!(v, 700);
+    assert_eq!(
+        usage,
+        Some(Usage {
+            allocations: 3,
+            bytes: 1700,
+            peak: 1500,
+            live: 0
+        })
+    );
+    let (_, usage) = measure(|| 1 + 1);
+    assert_eq!(usage, Some(Usage::default()));
+    assert_eq!(
+        Usage {
+            allocations: 1,
+            bytes: 10,
+            peak: 10,
+            live: 0
+        }
+        .then(Usage {
+            allocations: 2,
+            bytes: 4,
+            peak: 4,
+            live: 4
+        }),
+        Usage {
+            allocations: 3,
+            bytes: 14,
+            peak: 10,
+            live: 4
+        }
+    );
+    // A parsed input held while a part runs.
+    let (input, parse) = measure(|| std::hint::black_box(vec![0u8; 1 << 20]));
+    let (_, part) = measure(|| std::hint::black_box(vec![0u8; 1000]).len());
+    let day = parse.unwrap().then(part.unwrap());
+    assert_eq!((day.peak, day.live), ((1 << 20) + 1000, 1 << 20));
+    drop(input);
+    assert_eq!(Bytes(512).to_string(), "512B");
+    assert_eq!(Bytes(1536).to_string(), "1.5KiB");
+    assert_eq!(Bytes(3 * 1024 * 1024).to_string(), "3.0MiB");
+}
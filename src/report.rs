@@ -0,0 +1,572 @@
+use std::time::Duration;
+
+use crate::{
+    cli::Format,
+    mem::{Bytes, Usage},
+    solver::{Day, Solution},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail,
+    Unchecked,
+    Error,
+}
+
+impl Status {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Status::Pass => "pass",
+            Status::Fail => "fail",
+            Status::Unchecked => "unchecked",
+            Status::Error => "error",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DayResult {
+    pub day: u8,
+    // The input the day was solved for, set when `check` solves a day for several inputs.
+    pub input: Option<String>,
+    pub parse: Duration,
+    // Heap usage of the parse and all parts, if allocations were counted.
+    pub mem: Option<Usage>,
+    pub records: Vec<Record>,
+    // Excerpt of a malformed input pointing at the error, see `AocError::diagnostic`.
+    pub diagnostic: Option<String>,
+}
+
+impl DayResult {
+    pub fn new(day: &Day, solution: &Solution, expected: &[Option<String>; 2]) -> Self {
+        Self {
+            day: day.day,
+            input: None,
+            parse: solution.parse,
+            mem: solution.parse_mem.and_then(|parse| {
+                solution
+                    .parts
+                    .iter()
+                    .try_fold(parse, |mem, p| Some(mem.then(p.mem?)))
+            }),
+            records: Record::from_solution(day, solution, expected),
+            diagnostic: None,
+        }
+    }
+
+    // A day that could not be solved, reported as an error for each requested part.
+    pub fn error(day: &Day, part: Option<u8>, error: &str) -> Self {
+        Self {
+            day: day.day,
+            input: None,
+            parse: Duration::ZERO,
+            mem: None,
+            records: (1..=2)
+                .filter(|p| part.is_none_or(|a| a == *p))
+                .map(|p| Record {
+                    day: day.day,
+                    part: p,
+                    input: None,
+                    answer: String::new(),
+                    expected: None,
+                    parse: Duration::ZERO,
+                    elapsed: Duration::ZERO,
+                    mem: None,
+                    error: Some(error.to_string()),
+                })
+                .collect(),
+            diagnostic: None,
+        }
+    }
+
+    // A day whose solver failed after `solution`, the requested parts it has no answer for are
+    // reported as `error`.
+    pub fn partial(
+        day: &Day,
+        part: Option<u8>,
+        solution: &Solution,
+        expected: &[Option<String>; 2],
+        error: &str,
+    ) -> Self {
+        let mut result = Self::new(day, solution, expected);
+        let failed = Self::error(day, part, error)
+            .records
+            .into_iter()
+            .filter(|r| solution.parts.iter().all(|p| p.part != r.part))
+            .map(|r| Record {
+                parse: solution.parse,
+                ..r
+            })
+            .collect::<Vec<Record>>();
+        result.records.extend(failed);
+        result
+    }
+
+    // Labels the result and its records with the input they were solved for.
+    pub fn for_input(self, input: Option<&str>) -> Self {
+        Self {
+            input: input.map(String::from),
+            records: self
+                .records
+                .into_iter()
+                .map(|r| Record {
+                    input: input.map(String::from),
+                    ..r
+                })
+                .collect(),
+            ..self
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.parse + self.records.iter().map(|r| r.elapsed).sum::<Duration>()
+    }
+
+    pub fn failed(&self) -> bool {
+        matches!(self.status(), Status::Fail | Status::Error)
+    }
+
+    fn status(&self) -> Status {
+        let statuses = self.records.iter().map(|r| r.status());
+        if statuses.clone().any(|s| s == Status::Error) {
+            Status::Error
+        } else if statuses.clone().any(|s| s == Status::Fail) {
+            Status::Fail
+        } else if statuses.clone().all(|s| s == Status::Pass) {
+            Status::Pass
+        } else {
+            Status::Unchecked
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub input: Option<String>,
+    pub answer: String,
+    pub expected: Option<String>,
+    // The parse of the day, shared by its parts.
+    pub parse: Duration,
+    pub elapsed: Duration,
+    pub mem: Option<Usage>,
+    pub error: Option<String>,
+}
+
+impl Record {
+    // `expected` are the known answers for parts 1 and 2 of the input that was solved.
+    pub fn from_solution(
+        day: &Day,
+        solution: &Solution,
+        expected: &[Option<String>; 2],
+    ) -> Vec<Self> {
+        solution
+            .parts
+            .iter()
+            .map(|p| Self {
+                day: day.day,
+                part: p.part,
+                input: None,
+                answer: p.answer.clone(),
+                expected: expected[p.part as usize - 1].clone(),
+                parse: solution.parse,
+                elapsed: p.elapsed,
+                mem: p.mem,
+                error: None,
+            })
+            .collect()
+    }
+
+    pub fn status(&self) -> Status {
+        match (&self.error, &self.expected) {
+            (Some(_), _) => Status::Error,
+            (None, Some(e)) if *e == self.answer => Status::Pass,
+            (None, Some(_)) => Status::Fail,
+            (None, None) => Status::Unchecked,
+        }
+    }
+
+    // `day1 p2`, followed by the input if there is one.
+    fn label(&self) -> String {
+        let input = self
+            .input
+            .as_ref()
+            .map_or(String::new(), |i| format!(" ({i})"));
+        format!("day{} p{}{input}", self.day, self.part)
+    }
+}
+
+fn json_escape(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            '"' => "\\\"".to_string(),
+            '\\' => "\\\\".to_string(),
+            '\n' => "\\n".to_string(),
+            '\r' => "\\r".to_string(),
+            '\t' => "\\t".to_string(),
+            c if (c as u32) < 0x20 => format!("\\u{:04x}", c as u32),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+fn csv_escape(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn xml_escape(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            '&' => "&amp;".to_string(),
+            '<' => "&lt;".to_string(),
+            '>' => "&gt;".to_string(),
+            '"' => "&quot;".to_string(),
+            '\'' => "&apos;".to_string(),
+            '\n' => "&#10;".to_string(),
+            '\r' => "&#13;".to_string(),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+fn json(records: &[Record], threads: usize) -> String {
+    let rows = records
+        .iter()
+        .map(|r| {
+            format!(
+                "  {{\"day\": {}, \"part\": {}, {}\"answer\": \"{}\", \"parse_us\": {}, \"elapsed_us\": {}, \"threads\": {threads}, \"status\": \"{}\"{}{}}}",
+                r.day,
+                r.part,
+                r.input
+                    .as_ref()
+                    .map_or(String::new(), |i| format!("\"input\": \"{}\", ", json_escape(i))),
+                json_escape(&r.answer),
+                r.parse.as_micros(),
+                r.elapsed.as_micros(),
+                r.status().as_str(),
+                r.mem.map_or(String::new(), |m| format!(
+                    ", \"allocations\": {}, \"allocated_bytes\": {}, \"peak_bytes\": {}",
+                    m.allocations, m.bytes, m.peak
+                )),
+                r.error
+                    .as_ref()
+                    .map_or(String::new(), |e| format!(", \"error\": \"{}\"", json_escape(e)))
+            )
+        })
+        .collect::<Vec<String>>();
+    format!("[\n{}\n]", rows.join(",\n"))
+}
+
+// The allocation columns are only there if allocations were counted.
+fn csv(records: &[Record], threads: usize) -> String {
+    let mem = records.iter().any(|r| r.mem.is_some());
+    let mut header = "day,part,input,answer,parse_us,elapsed_us,threads,status,error".to_string();
+    if mem {
+        header.push_str(",allocations,allocated_bytes,peak_bytes");
+    }
+    std::iter::once(header)
+        .chain(records.iter().map(|r| {
+            let usage = match (mem, r.mem) {
+                (true, Some(m)) => format!(",{},{},{}", m.allocations, m.bytes, m.peak),
+                (true, None) => ",,,".to_string(),
+                (false, _) => String::new(),
+            };
+            format!(
+                "{},{},{},{},{},{},{threads},{},{}{usage}",
+                r.day,
+                r.part,
+                csv_escape(r.input.as_deref().unwrap_or_default()),
+                csv_escape(&r.answer),
+                r.parse.as_micros(),
+                r.elapsed.as_micros(),
+                r.status().as_str(),
+                csv_escape(r.error.as_deref().unwrap_or_default())
+            )
+        }))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn junit(records: &[Record], threads: usize) -> String {
+    let count = |status| records.iter().filter(|r| r.status() == status).count();
+    let (failures, errors) = (count(Status::Fail), count(Status::Error));
+    let total = records.iter().map(|r| r.elapsed).sum::<Duration>();
+    let cases = records
+        .iter()
+        .map(|r| {
+            let body = match (r.status(), &r.expected, &r.error) {
+                (Status::Fail, Some(e), _) => format!(
+                    "<failure message=\"expected {}, got {}\"/>",
+                    xml_escape(e),
+                    xml_escape(&r.answer)
+                ),
+                (Status::Error, _, Some(e)) => format!("<error message=\"{}\"/>", xml_escape(e)),
+                _ => String::new(),
+            };
+            format!(
+                "  <testcase classname=\"day{}\" name=\"part{}{}\" time=\"{:.6}\">{body}<system-out>{}</system-out></testcase>",
+                r.day,
+                r.part,
+                r.input
+                    .as_ref()
+                    .map_or(String::new(), |i| format!(" ({})", xml_escape(i))),
+                r.elapsed.as_secs_f64(),
+                xml_escape(&r.answer)
+            )
+        })
+        .collect::<Vec<String>>();
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuite name=\"aoc2021\" tests=\"{}\" failures=\"{failures}\" errors=\"{errors}\" time=\"{:.6}\">\n  <properties><property name=\"threads\" value=\"{threads}\"/></properties>\n{}\n</testsuite>",
+        records.len(),
+        total.as_secs_f64(),
+        cases.join("\n")
+    )
+}
+
+fn text(records: &[Record]) -> String {
+    records
+        .iter()
+        .map(|r| match (r.status(), &r.expected, &r.error) {
+            (Status::Pass, _, _) => format!("{}: {} ok", r.label(), r.answer),
+            (Status::Fail, Some(e), _) => format!("{}: {} FAIL, expected {e}", r.label(), r.answer),
+            (Status::Error, _, Some(e)) => format!("{}: ERROR, {e}", r.label()),
+            _ => format!("{}: {}", r.label(), r.answer),
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn duration(d: Option<Duration>) -> String {
+    d.map_or("-".to_string(), |d| format!("{d:.1?}"))
+}
+
+// Allocation count, bytes allocated and peak heap, or dashes if allocations were not counted.
+fn usage(mem: Option<Usage>) -> String {
+    mem.map_or(format!(" {:>10} {:>10} {:>10}", "-", "-", "-"), |m| {
+        format!(
+            " {:>10} {:>10} {:>10}",
+            m.allocations,
+            Bytes(m.bytes).to_string(),
+            Bytes(m.peak).to_string()
+        )
+    })
+}
+
+// Per-day timings in day order, `wall` is the elapsed time of the whole run on `threads` threads.
+// Heap usage is shown if allocations were counted.
+pub fn summary(days: &[DayResult], wall: Duration, threads: usize) -> String {
+    let part = |d: &DayResult, p: u8| d.records.iter().find(|r| r.part == p).map(|r| r.elapsed);
+    let cpu = days.iter().map(|d| d.elapsed()).sum::<Duration>();
+    let failed = days.iter().filter(|d| d.failed()).count();
+    let mem = days.iter().any(|d| d.mem.is_some());
+    std::iter::once(format!(
+        "{:>5} {:>10} {:>10} {:>10} {:>10}{}  status",
+        "day",
+        "parse",
+        "part 1",
+        "part 2",
+        "total",
+        if mem {
+            format!(" {:>10} {:>10} {:>10}", "allocs", "allocated", "peak")
+        } else {
+            String::new()
+        }
+    ))
+    .chain(days.iter().map(|d| {
+        format!(
+            "{:>5} {:>10} {:>10} {:>10} {:>10}{}  {}",
+            d.day,
+            duration(Some(d.parse)),
+            duration(part(d, 1)),
+            duration(part(d, 2)),
+            duration(Some(d.elapsed())),
+            if mem { usage(d.mem) } else { String::new() },
+            match d.status() {
+                Status::Pass => "ok",
+                Status::Fail => "FAIL",
+                Status::Unchecked => "-",
+                Status::Error => "ERROR",
+            }
+        )
+    }))
+    .chain(std::iter::once(format!(
+        "{} day(s), {failed} failed, {threads} thread(s), wall time {}, sum of day times {} ({:.2}x)",
+        days.len(),
+        duration(Some(wall)),
+        duration(Some(cpu)),
+        cpu.as_secs_f64() / wall.as_secs_f64().max(f64::EPSILON)
+    )))
+    .collect::<Vec<String>>()
+    .join("\n")
+}
+
+// `threads` is the size of the thread pool the records were solved on.
+pub fn render(format: Format, records: &[Record], threads: usize) -> String {
+    match format {
+        Format::Text => text(records),
+        Format::Json => json(records, threads),
+        Format::Csv => csv(records, threads),
+        Format::Junit => junit(records, threads),
+    }
+}
+
+#[test]
+fn test_summary() {
+    let record = |part, expected: Option<&str>| Record {
+        day: 3,
+        part,
+        input: None,
+        answer: "7".to_string(),
+        expected: expected.map(String::from),
+        parse: Duration::from_millis(1),
+        elapsed: Duration::from_millis(2),
+        mem: None,
+        error: None,
+    };
+    let days = [
+        DayResult {
+            day: 3,
+            input: None,
+            parse: Duration::from_millis(1),
+            mem: None,
+            records: vec![record(1, Some("7")), record(2, Some("8"))],
+            diagnostic: None,
+        },
+        DayResult {
+            day: 12,
+            input: None,
+            parse: Duration::from_millis(1),
+            mem: None,
+            records: vec![record(2, None)],
+            diagnostic: None,
+        },
+        DayResult::error(crate::solver::find(2021, 5).unwrap(), Some(1), "boom"),
+    ];
+    assert_eq!(
+        summary(&days, Duration::from_millis(4), 2),
+        "  day      parse     part 1     part 2      total  status
+    3      1.0ms      2.0ms      2.0ms      5.0ms  FAIL
+   12      1.0ms          -      2.0ms      3.0ms  -
+    5      0.0ns      0.0ns          -      0.0ns  ERROR
+3 day(s), 2 failed, 2 thread(s), wall time 4.0ms, sum of day times 8.0ms (2.00x)"
+    );
+    assert_eq!(
+        render(Format::Text, &days[2].records, 2),
+        "day5 p1: ERROR, boom"
+    );
+    assert_eq!(
+        render(Format::Json, &days[2].records, 2),
+        r#"[
+  {"day": 5, "part": 1, "answer": "", "parse_us": 0, "elapsed_us": 0, "threads": 2, "status": "error", "error": "boom"}
+]"#
+    );
+
+    let mem = Usage {
+        allocations: 12,
+        bytes: 3 << 20,
+        peak: 1536,
+        live: 0,
+    };
+    let counted = DayResult {
+        mem: Some(mem),
+        records: vec![Record {
+            mem: Some(mem),
+            ..record(1, Some("7"))
+        }],
+        ..days[0].clone()
+    };
+    assert_eq!(
+        summary(&[counted.clone(), days[2].clone()], Duration::from_millis(4), 1)
+            .lines()
+            .take(3)
+            .collect::<Vec<&str>>(),
+        [
+            "  day      parse     part 1     part 2      total     allocs  allocated       peak  status",
+            "    3      1.0ms      2.0ms          -      3.0ms         12     3.0MiB     1.5KiB  ok",
+            "    5      0.0ns      0.0ns          -      0.0ns          -          -          -  ERROR"
+        ]
+    );
+    assert_eq!(
+        render(Format::Json, &counted.records, 1),
+        r#"[
+  {"day": 3, "part": 1, "answer": "7", "parse_us": 1000, "elapsed_us": 2000, "threads": 1, "status": "pass", "allocations": 12, "allocated_bytes": 3145728, "peak_bytes": 1536}
+]"#
+    );
+    assert_eq!(
+        render(Format::Csv, &[counted.records[0].clone(), days[2].records[0].clone()], 1),
+        "day,part,input,answer,parse_us,elapsed_us,threads,status,error,allocations,allocated_bytes,peak_bytes
+3,1,,7,1000,2000,1,pass,,12,3145728,1536
+5,1,,,0,0,1,error,boom,,,"
+    );
+}
+
+#[test]
+fn test_render() {
+    let records = [
+        Record {
+            day: 1,
+            part: 1,
+            input: None,
+            answer: "7".to_string(),
+            expected: Some("7".to_string()),
+            parse: Duration::from_micros(40),
+            elapsed: Duration::from_micros(12),
+            mem: None,
+            error: None,
+        },
+        Record {
+            day: 13,
+            part: 2,
+            input: Some("aoc_13_sample.input".to_string()),
+            answer: "\nX, \"X\"".to_string(),
+            expected: Some("Y".to_string()),
+            parse: Duration::from_micros(5),
+            elapsed: Duration::from_micros(3),
+            mem: None,
+            error: None,
+        },
+        Record {
+            day: 17,
+            part: 1,
+            input: None,
+            answer: "45".to_string(),
+            expected: None,
+            parse: Duration::from_micros(250),
+            elapsed: Duration::from_micros(1500),
+            mem: None,
+            error: None,
+        },
+    ];
+    assert_eq!(
+        render(Format::Text, &records, 4),
+        "day1 p1: 7 ok\nday13 p2 (aoc_13_sample.input): \nX, \"X\" FAIL, expected Y\nday17 p1: 45"
+    );
+    assert_eq!(
+        render(Format::Json, &records, 4),
+        r#"[
+  {"day": 1, "part": 1, "answer": "7", "parse_us": 40, "elapsed_us": 12, "threads": 4, "status": "pass"},
+  {"day": 13, "part": 2, "input": "aoc_13_sample.input", "answer": "\nX, \"X\"", "parse_us": 5, "elapsed_us": 3, "threads": 4, "status": "fail"},
+  {"day": 17, "part": 1, "answer": "45", "parse_us": 250, "elapsed_us": 1500, "threads": 4, "status": "unchecked"}
+]"#
+    );
+    assert_eq!(
+        render(Format::Csv, &records, 4),
+        "day,part,input,answer,parse_us,elapsed_us,threads,status,error\n1,1,,7,40,12,4,pass,\n13,2,aoc_13_sample.input,\"\nX, \"\"X\"\"\",5,3,4,fail,\n17,1,,45,250,1500,4,unchecked,"
+    );
+    assert_eq!(
+        render(Format::Junit, &records, 4),
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuite name="aoc2021" tests="3" failures="1" errors="0" time="0.001515">
+  <properties><property name="threads" value="4"/></properties>
+  <testcase classname="day1" name="part1" time="0.000012"><system-out>7</system-out></testcase>
+  <testcase classname="day13" name="part2 (aoc_13_sample.input)" time="0.000003"><failure message="expected Y, got &#10;X, &quot;X&quot;"/><system-out>&#10;X, &quot;X&quot;</system-out></testcase>
+  <testcase classname="day17" name="part1" time="0.001500"><system-out>45</system-out></testcase>
+</testsuite>"#
+    );
+}
@@ -0,0 +1,388 @@
+use rayon::{prelude::*, ThreadPool, ThreadPoolBuilder};
+use std::{
+    any::Any,
+    cell::Cell,
+    panic,
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    answers::Answers,
+    cancel::{self, Cancelled},
+    input::{self, Source},
+    report::{DayResult, Record},
+    solver::{Day, Progress, Solution},
+};
+
+pub struct Job {
+    pub day: &'static Day,
+    // The input file name, set when a day is solved for several inputs.
+    pub name: Option<String>,
+    // Input loading errors are reported per day, like solver panics.
+    pub input: Result<String, String>,
+    // Known answers for parts 1 and 2 of the input.
+    pub expected: [Option<String>; 2],
+}
+
+pub fn load(days: Vec<&'static Day>, source: impl Fn(u8) -> Source, answers: &Answers) -> Vec<Job> {
+    days.into_iter()
+        .map(|day| {
+            let source = source(day.day);
+            Job {
+                day,
+                name: None,
+                input: input::load(&source),
+                expected: answers.expected(day.day, &source),
+            }
+        })
+        .collect()
+}
+
+// A job for every input listed in `answers`, days without any are solved for their
+// puzzle input without known answers.
+pub fn load_listed(days: Vec<&'static Day>, answers: &Answers) -> Vec<Job> {
+    days.into_iter()
+        .flat_map(|day| {
+            let mut names = answers
+                .inputs(day.day)
+                .into_iter()
+                .map(String::from)
+                .collect::<Vec<String>>();
+            if names.is_empty() {
+                names.push(input::file_name(day.day));
+            }
+            names.into_iter().map(move |name| Job {
+                day,
+                input: input::load(&Source::File(answers.dir.join(&name))),
+                expected: answers.get(day.day, &name),
+                name: Some(name),
+            })
+        })
+        .collect()
+}
+
+thread_local! {
+    static PANIC_LOCATION: Cell<Option<String>> = const { Cell::new(None) };
+    // Set while `catch_solver` runs a solver on this thread.
+    static SOLVING: Cell<bool> = const { Cell::new(false) };
+}
+
+// Solver panics are reported per day, so only remember where they happened. Any other panic
+// goes to the previous hook as usual.
+pub fn install_panic_hook() {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if SOLVING.get() {
+            PANIC_LOCATION.set(info.location().map(|l| l.to_string()));
+        } else {
+            previous(info);
+        }
+    }));
+}
+
+// Runs the solver call `f`, catching its panics for `panic_message`.
+pub fn catch_solver<T>(f: impl FnOnce() -> T) -> Result<T, Box<dyn Any + Send>> {
+    let solving = SOLVING.replace(true);
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(f));
+    SOLVING.set(solving);
+    result
+}
+
+fn timed_out(timeout: Option<Duration>) -> String {
+    format!("timed out after {:?}", timeout.unwrap_or_default())
+}
+
+pub fn panic_message(payload: Box<dyn Any + Send>, timeout: Option<Duration>) -> String {
+    if payload.is::<Cancelled>() {
+        return timed_out(timeout);
+    }
+    let location = PANIC_LOCATION
+        .take()
+        .map_or(String::new(), |l| format!(" at {l}"));
+    match payload.downcast::<String>() {
+        Ok(s) => format!("panicked{location}: {s}"),
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(s) => format!("panicked{location}: {s}"),
+            Err(_) => format!("panicked{location}"),
+        },
+    }
+}
+
+// The parts of `done` keep their answers, the other requested parts fail with `error`. The day
+// ran for `total`, what the phases in `done` do not account for was spent on the one running
+// when it failed, the parse or the first part without an answer.
+fn failed(
+    day: &Day,
+    part: Option<u8>,
+    done: Option<&Solution>,
+    expected: &[Option<String>; 2],
+    error: &str,
+    total: Duration,
+) -> DayResult {
+    let accounted = done.map_or(Duration::ZERO, |s| {
+        s.parse + s.parts.iter().map(|p| p.elapsed).sum::<Duration>()
+    });
+    let spent = total.saturating_sub(accounted);
+    let Some(solution) = done else {
+        let result = DayResult::error(day, part, error);
+        return DayResult {
+            parse: spent,
+            records: result
+                .records
+                .into_iter()
+                .map(|r| Record { parse: spent, ..r })
+                .collect(),
+            ..result
+        };
+    };
+    let mut result = DayResult::partial(day, part, solution, expected, error);
+    if let Some(r) = result.records.iter_mut().find(|r| r.error.is_some()) {
+        r.elapsed = spent;
+    }
+    result
+}
+
+// Solves `input` with `token` as the current token, `progress` sees the solution so far.
+fn attempt(
+    day: &Day,
+    input: &str,
+    part: Option<u8>,
+    expected: &[Option<String>; 2],
+    token: cancel::Token,
+    timeout: Option<Duration>,
+    progress: Progress,
+) -> DayResult {
+    let (mut done, start) = (None, Instant::now());
+    let solved = catch_solver(|| {
+        cancel::with_token(token, || {
+            (day.solve)(input, part, &mut |s| {
+                // A parse or part that took too long without polling still timed out.
+                cancel::check();
+                progress(s);
+                done = Some(s.clone());
+            })
+        })
+    });
+    match solved {
+        Ok(Ok(solution)) => DayResult::new(day, &solution, expected),
+        Ok(Err(e)) => DayResult {
+            diagnostic: Some(e.diagnostic(input)),
+            ..DayResult::error(day, part, &format!("malformed input, {e}"))
+        },
+        Err(payload) => failed(
+            day,
+            part,
+            done.as_ref(),
+            expected,
+            &panic_message(payload, timeout),
+            start.elapsed(),
+        ),
+    }
+}
+
+enum Event {
+    Progress(Solution),
+    Done(DayResult),
+}
+
+// Solves on a thread of its own so that a day which never polls `cancel::check` is given up
+// on once its time is up. There is no way to stop such a thread, it is left to finish in the
+// background and takes a core from the days solved after it until then.
+fn watched(job: &Job, input: &str, part: Option<u8>, timeout: Option<Duration>) -> DayResult {
+    let start = Instant::now();
+    let token = cancel::Token::new(timeout);
+    let (sender, receiver) = mpsc::channel();
+    let (day, input, expected) = (job.day, input.to_string(), job.expected.clone());
+    thread::spawn(move || {
+        let progress = sender.clone();
+        let result = attempt(day, &input, part, &expected, token, timeout, &mut |s| {
+            let _ = progress.send(Event::Progress(s.clone()));
+        });
+        let _ = sender.send(Event::Done(result));
+    });
+    let mut done = None;
+    loop {
+        match receiver.recv_timeout(token.remaining().unwrap_or_default()) {
+            Ok(Event::Done(result)) => return result,
+            Ok(Event::Progress(solution)) => done = Some(solution),
+            Err(_) => {
+                return failed(
+                    job.day,
+                    part,
+                    done.as_ref(),
+                    &job.expected,
+                    &timed_out(timeout),
+                    start.elapsed(),
+                )
+            }
+        }
+    }
+}
+
+// Solves a single job, a failing or malformed input, a panicking solver or a
+// solver running out of time only fails this day. Parts that were solved before
+// another one failed keep their answers.
+pub fn solve_one(job: &Job, part: Option<u8>, timeout: Option<Duration>) -> DayResult {
+    let input = match &job.input {
+        Ok(input) => input,
+        Err(e) => return DayResult::error(job.day, part, e).for_input(job.name.as_deref()),
+    };
+    let result = match timeout {
+        Some(_) => watched(job, input, part, timeout),
+        None => attempt(
+            job.day,
+            input,
+            part,
+            &job.expected,
+            cancel::Token::new(None),
+            None,
+            &mut |_| {},
+        ),
+    };
+    result.for_input(job.name.as_deref())
+}
+
+// A pool of `jobs` threads, or one per core if None.
+pub fn thread_pool(jobs: Option<usize>) -> Result<ThreadPool, String> {
+    ThreadPoolBuilder::new()
+        .num_threads(jobs.unwrap_or(0))
+        .build()
+        .map_err(|e| format!("cannot create thread pool: {e}"))
+}
+
+// Solves all jobs in parallel on `pool`, results are returned in job order.
+pub fn solve_all(
+    pool: &ThreadPool,
+    jobs: &[Job],
+    part: Option<u8>,
+    timeout: Option<Duration>,
+) -> Vec<DayResult> {
+    pool.install(|| {
+        jobs.par_iter()
+            .map(|job| solve_one(job, part, timeout))
+            .collect()
+    })
+}
+
+pub fn records(days: &[DayResult]) -> Vec<Record> {
+    days.iter().flat_map(|d| d.records.clone()).collect()
+}
+
+#[test]
+fn test_catch_solver() {
+    assert_eq!(catch_solver(|| 1).ok(), Some(1));
+    let payload = catch_solver(|| panic!("boom")).unwrap_err();
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"boom"));
+    // Panics after the solver returned are not the solver's.
+    assert!(!SOLVING.get());
+}
+
+#[test]
+fn test_solve_one() {
+    use crate::{error::AocError, solver::Solver};
+
+    // Part 2 runs until it is cancelled, for "sleep" without ever polling.
+    struct Slow;
+
+    impl Solver for Slow {
+        type Input<'a> = &'a str;
+        type Output1 = usize;
+        type Output2 = usize;
+
+        fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+            Ok(input)
+        }
+
+        fn part1(input: &Self::Input<'_>) -> usize {
+            input.len()
+        }
+
+        fn part2(input: &Self::Input<'_>) -> usize {
+            loop {
+                match *input {
+                    "sleep" => thread::sleep(Duration::from_secs(10)),
+                    _ => cancel::check(),
+                }
+            }
+        }
+    }
+
+    static SLOW: Day = Day {
+        year: crate::solver::YEAR,
+        day: 25,
+        solve: crate::solver::solve::<Slow>,
+    };
+    let job = Job {
+        day: &SLOW,
+        name: None,
+        input: Ok("abc".to_string()),
+        expected: [Some("3".to_string()), None],
+    };
+    let timeout = Some(Duration::from_millis(20));
+    let result = solve_one(&job, None, timeout);
+    let parts = records(&[result]);
+    assert_eq!(parts.len(), 2);
+    assert_eq!(parts[0].status(), crate::report::Status::Pass);
+    assert_eq!(parts[1].error.as_deref(), Some("timed out after 20ms"));
+    let result = solve_one(&job, Some(2), timeout);
+    assert_eq!(result.records.len(), 1);
+    assert_eq!(result.records[0].part, 2);
+
+    let job = Job {
+        input: Ok("sleep".to_string()),
+        expected: [Some("5".to_string()), None],
+        ..job
+    };
+    let now = std::time::Instant::now();
+    let result = solve_one(&job, None, timeout);
+    assert!(now.elapsed() < Duration::from_secs(5));
+    let parts = records(std::slice::from_ref(&result));
+    assert_eq!(parts[0].status(), crate::report::Status::Pass);
+    assert_eq!(parts[1].error.as_deref(), Some("timed out after 20ms"));
+    // The day used up its time, most of it in part 2.
+    assert!(result.elapsed() >= Duration::from_millis(20));
+    assert!(parts[1].elapsed >= Duration::from_millis(10));
+}
+
+#[test]
+fn test_solve_all() {
+    let day = |d| crate::solver::find(crate::solver::YEAR, d).unwrap();
+    let jobs = [
+        Job {
+            day: day(17),
+            name: None,
+            input: Ok("target area: x=20..30, y=-10..-5".to_string()),
+            expected: [Some("45".to_string()), None],
+        },
+        Job {
+            day: day(1),
+            name: Some("aoc_1.input".to_string()),
+            input: Err("cannot read input".to_string()),
+            expected: Default::default(),
+        },
+        Job {
+            day: day(2),
+            name: None,
+            input: Ok("forward 5\nsideways 2".to_string()),
+            expected: Default::default(),
+        },
+    ];
+    let days = solve_all(&thread_pool(Some(2)).unwrap(), &jobs, Some(1), None);
+    let records = records(&days);
+    assert_eq!(records.len(), 3);
+    assert_eq!((records[0].day, records[0].answer.as_str()), (17, "45"));
+    assert_eq!(records[0].status(), crate::report::Status::Pass);
+    assert_eq!(records[1].error.as_deref(), Some("cannot read input"));
+    assert_eq!(records[1].input.as_deref(), Some("aoc_1.input"));
+    assert!(days[1].failed());
+    assert_eq!(
+        records[2].error.as_deref(),
+        Some("malformed input, line 2, column 1: unknown direction 'sideways'")
+    );
+    assert_eq!(
+        days[2].diagnostic.as_deref(),
+        Some("line 2, column 1: unknown direction 'sideways'\n2 | sideways 2\n  | ^")
+    );
+}
@@ -0,0 +1,192 @@
+// Generating and registering new days, used by the `new` command.
+
+pub const TEMPLATE: &str = include_str!("template.rs");
+
+// Marks the template's test stub as ignored while the template itself is compiled.
+const IGNORE_MARKER: &str = "#[ignore = \"template\"]";
+
+const MAX_WIDTH: usize = 100;
+
+// The source of `src/dayN.rs`, the test stub fails until the sample answers are filled in.
+pub fn day_source(template: &str, day: u8) -> String {
+    template
+        .lines()
+        .filter(|l| l.trim() != IGNORE_MARKER)
+        .map(|l| l.replace("XX", &day.to_string()) + template_eol(template))
+        .collect()
+}
+
+fn template_eol(template: &str) -> &'static str {
+    if template.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    }
+}
+
+fn day_number(module: &str) -> Option<u8> {
+    module.strip_prefix("day")?.parse().ok()
+}
+
+// Adds `pub mod dayN;` to `src/lib.rs`, keeping the modules sorted.
+pub fn register_module(lib: &str, day: u8) -> Result<String, String> {
+    let module = format!("day{day}");
+    let mut lines = lib.lines().collect::<Vec<&str>>();
+    let mods = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, l)| Some((i, l.strip_prefix("pub mod ")?.strip_suffix(';')?)))
+        .collect::<Vec<(usize, &str)>>();
+    if mods.iter().any(|(_, m)| *m == module) {
+        return Err(format!("day{day} is already a module of src/lib.rs"));
+    }
+    let line = format!("pub mod {module};");
+    let at = mods
+        .iter()
+        .find(|(_, m)| *m > module.as_str())
+        .or(mods.last())
+        .map(|(i, m)| if *m > module.as_str() { *i } else { i + 1 })
+        .ok_or("src/lib.rs declares no modules")?;
+    lines.insert(at, &line);
+    Ok(lines.join("\n") + "\n")
+}
+
+// Formats `use crate::{...};` the way rustfmt does.
+fn format_use(items: &[String]) -> String {
+    let single = format!("use crate::{{{}}};", items.join(", "));
+    if single.len() <= MAX_WIDTH {
+        return single;
+    }
+    let mut lines: Vec<String> = Vec::new();
+    for item in items {
+        match lines.last_mut() {
+            // Indent, the line so far, a space and a comma around the item.
+            Some(l) if 4 + l.len() + 2 + item.len() <= MAX_WIDTH => {
+                l.push_str(&format!(" {item},"));
+            }
+            _ => lines.push(format!("{item},")),
+        }
+    }
+    format!(
+        "use crate::{{\n{}\n}};",
+        lines
+            .iter()
+            .map(|l| format!("    {l}"))
+            .collect::<Vec<String>>()
+            .join("\n")
+    )
+}
+
+// Adds day `day` to the imports and to `DAYS` in `src/solver.rs`.
+pub fn register_day(solver: &str, day: u8) -> Result<String, String> {
+    let err = |what: &str| format!("cannot register day{day} in src/solver.rs: {what}");
+    let (head, rest) = solver
+        .split_once("use crate::{")
+        .ok_or_else(|| err("no day imports"))?;
+    let (imports, rest) = rest.split_once("};").ok_or_else(|| err("no day imports"))?;
+    let mut items = imports
+        .split(',')
+        .map(str::trim)
+        .filter(|i| !i.is_empty())
+        .map(String::from)
+        .collect::<Vec<String>>();
+    if items.iter().any(|i| day_number(i) == Some(day)) {
+        return Err(err("already registered"));
+    }
+    items.push(format!("day{day}"));
+    items.sort();
+
+    let (before, after) = rest
+        .split_once("pub static DAYS: [Day; ")
+        .ok_or_else(|| err("no DAYS"))?;
+    let (len, after) = after.split_once("] = [").ok_or_else(|| err("no DAYS"))?;
+    let len = len.parse::<usize>().map_err(|_| err("no DAYS"))?;
+    let (entries, after) = after.split_once("\n];").ok_or_else(|| err("no DAYS"))?;
+    let entry = format!("    Day::new::<day{day}::Day{day}>({day}),");
+    let mut lines = entries.lines().collect::<Vec<&str>>();
+    let at = lines
+        .iter()
+        .position(|l| {
+            l.trim_start()
+                .strip_prefix("Day::new::<day")
+                .and_then(|l| l.split_once("::"))
+                .and_then(|(d, _)| d.parse::<u8>().ok())
+                .is_some_and(|d| d > day)
+        })
+        .unwrap_or(lines.len());
+    lines.insert(at, &entry);
+    Ok(format!(
+        "{head}{}{before}pub static DAYS: [Day; {}] = [{}\n];{after}",
+        format_use(&items),
+        len + 1,
+        lines.join("\n")
+    ))
+}
+
+#[test]
+fn test_day_source() {
+    let source = day_source(TEMPLATE, 19);
+    assert!(!source.contains("XX") && !source.contains(IGNORE_MARKER));
+    assert!(source.contains("pub struct Day19;"));
+    assert!(source.contains("src/inputs/aoc_19_sample.input"));
+}
+
+#[test]
+fn test_register() {
+    let lib = "pub mod day1;\npub mod day10;\npub mod day2;\npub mod error;\n";
+    assert_eq!(
+        register_module(lib, 19),
+        Ok("pub mod day1;\npub mod day10;\npub mod day19;\npub mod day2;\npub mod error;\n".into())
+    );
+    assert!(register_module(lib, 2).is_err());
+
+    let solver = "use crate::{day1, day10, day2};
+use crate::error::AocError;
+
+pub static DAYS: [Day; 3] = [
+    Day::new::<day1::Day1>(1),
+    Day::new::<day2::Day2>(2),
+    Day::new::<day10::Day10>(10),
+];
+";
+    assert_eq!(
+        register_day(solver, 5),
+        Ok("use crate::{day1, day10, day2, day5};
+use crate::error::AocError;
+
+pub static DAYS: [Day; 4] = [
+    Day::new::<day1::Day1>(1),
+    Day::new::<day2::Day2>(2),
+    Day::new::<day5::Day5>(5),
+    Day::new::<day10::Day10>(10),
+];
+"
+        .into())
+    );
+    assert!(register_day(solver, 10).is_err());
+
+    // The registry of this crate stays formatted.
+    let solver = include_str!("solver.rs").replace("\r\n", "\n");
+    let registered = register_day(&solver, 25).unwrap();
+    let words = registered.split_whitespace().collect::<Vec<&str>>();
+    assert!(words.windows(2).any(|w| w == ["day25,", "day3,"]));
+    assert!(registered.contains("    Day::new::<day25::Day25>(25),\n];"));
+    assert!(registered.lines().all(|l| l.len() <= MAX_WIDTH));
+}
+
+#[test]
+fn test_format_use() {
+    let mut items = (1..=20).map(|d| format!("day{d}")).collect::<Vec<String>>();
+    items.sort();
+    assert_eq!(
+        format_use(&items),
+        "use crate::{
+    day1, day10, day11, day12, day13, day14, day15, day16, day17, day18, day19, day2, day20, day3,
+    day4, day5, day6, day7, day8, day9,
+};"
+    );
+    assert_eq!(
+        format_use(&["day1".into(), "day2".into()]),
+        "use crate::{day1, day2};"
+    );
+}
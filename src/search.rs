@@ -0,0 +1,81 @@
+// Shortest paths over states joined by weighted moves.
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap},
+    hash::Hash,
+};
+
+use crate::cancel;
+
+#[derive(Debug, PartialEq, Eq)]
+struct Entry<S> {
+    state: S,
+    cost: usize,
+}
+
+// The heap pops the lowest cost first.
+impl<S: Eq> PartialOrd for Entry<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S: Eq> Ord for Entry<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.cmp(&self.cost)
+    }
+}
+
+// The lowest cost of getting from `start` to a state `goal` holds for, None if there is no
+// such state. `successors` gives the states one move away and what the move costs.
+pub fn dijkstra<S, I>(
+    start: S,
+    goal: impl Fn(&S) -> bool,
+    mut successors: impl FnMut(&S) -> I,
+) -> Option<usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    let mut costs: HashMap<S, usize> = HashMap::new();
+    let mut to_visit = BinaryHeap::new();
+    costs.insert(start.clone(), 0);
+    to_visit.push(Entry {
+        state: start,
+        cost: 0,
+    });
+    while let Some(Entry { state, cost }) = to_visit.pop() {
+        cancel::check();
+        if goal(&state) {
+            return Some(cost);
+        }
+        // Visited already at a lower cost.
+        if costs.get(&state).is_some_and(|c| *c < cost) {
+            continue;
+        }
+        for (next, step) in successors(&state) {
+            let next_cost = cost + step;
+            if costs.get(&next).is_none_or(|c| next_cost < *c) {
+                costs.insert(next.clone(), next_cost);
+                to_visit.push(Entry {
+                    state: next,
+                    cost: next_cost,
+                });
+            }
+        }
+    }
+    None
+}
+
+#[test]
+fn test_dijkstra() {
+    // Steps of 1 and 3 cost 1 and 2, the cheapest way to 7 is 3 + 3 + 1.
+    let steps = |n: &u32| {
+        [(n + 1, 1), (n + 3, 2)]
+            .into_iter()
+            .filter(|(n, _)| *n <= 7)
+    };
+    assert_eq!(dijkstra(0, |n| *n == 7, steps), Some(5));
+    assert_eq!(dijkstra(0, |n| *n == 0, steps), Some(0));
+    assert_eq!(dijkstra(0, |n| *n == 8, steps), None);
+}
@@ -0,0 +1,175 @@
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use crate::error::AocError;
+use crate::mem::{self, Usage};
+use crate::{
+    day1, day10, day11, day12, day13, day14, day15, day16, day17, day18, day19, day2, day20, day21,
+    day22, day23, day3, day4, day5, day6, day7, day8, day9,
+};
+
+pub trait Solver {
+    type Input<'a>;
+    type Output1: Display;
+    type Output2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError>;
+    fn part1(input: &Self::Input<'_>) -> Self::Output1;
+    fn part2(input: &Self::Input<'_>) -> Self::Output2;
+}
+
+#[derive(Debug, Clone)]
+pub struct PartResult {
+    pub part: u8,
+    pub answer: String,
+    pub elapsed: Duration,
+    // Heap usage, if `mem` counts allocations.
+    pub mem: Option<Usage>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Solution {
+    pub parse: Duration,
+    pub parse_mem: Option<Usage>,
+    pub parts: Vec<PartResult>,
+}
+
+fn timed(part: u8, f: impl FnOnce() -> String) -> PartResult {
+    let now = Instant::now();
+    let (answer, mem) = mem::measure(f);
+    PartResult {
+        part,
+        answer,
+        elapsed: now.elapsed(),
+        mem,
+    }
+}
+
+// Solves the requested part, or both if `part` is None. `progress` sees the solution so far
+// after the parse and after each part, so a caller catching a panicking part keeps the others.
+pub fn solve<S: Solver>(
+    input: &str,
+    part: Option<u8>,
+    progress: Progress,
+) -> Result<Solution, AocError> {
+    let now = Instant::now();
+    let (parsed, parse_mem) = mem::measure(|| S::parse(input));
+    let parse = now.elapsed();
+    let parsed = parsed?;
+    let mut solution = Solution {
+        parse,
+        parse_mem,
+        parts: Vec::new(),
+    };
+    progress(&solution);
+    for p in (1..=2).filter(|p| part.is_none_or(|a| a == *p)) {
+        solution.parts.push(match p {
+            1 => timed(1, || S::part1(&parsed).to_string()),
+            _ => timed(2, || S::part2(&parsed).to_string()),
+        });
+        progress(&solution);
+    }
+    Ok(solution)
+}
+
+// See `solve`.
+pub type Progress<'a> = &'a mut dyn FnMut(&Solution);
+
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub solve: fn(&str, Option<u8>, Progress) -> Result<Solution, AocError>,
+}
+
+pub const YEAR: u16 = 2021;
+
+impl Day {
+    const fn new<S: Solver>(day: u8) -> Self {
+        Self {
+            year: YEAR,
+            day,
+            solve: solve::<S>,
+        }
+    }
+}
+
+// Keep sorted by (year, day), `find` relies on it.
+pub static DAYS: [Day; 23] = [
+    Day::new::<day1::Day1>(1),
+    Day::new::<day2::Day2>(2),
+    Day::new::<day3::Day3>(3),
+    Day::new::<day4::Day4>(4),
+    Day::new::<day5::Day5>(5),
+    Day::new::<day6::Day6>(6),
+    Day::new::<day7::Day7>(7),
+    Day::new::<day8::Day8>(8),
+    Day::new::<day9::Day9>(9),
+    Day::new::<day10::Day10>(10),
+    Day::new::<day11::Day11>(11),
+    Day::new::<day12::Day12>(12),
+    Day::new::<day13::Day13>(13),
+    Day::new::<day14::Day14>(14),
+    Day::new::<day15::Day15>(15),
+    Day::new::<day16::Day16>(16),
+    Day::new::<day17::Day17>(17),
+    Day::new::<day18::Day18>(18),
+    Day::new::<day19::Day19>(19),
+    Day::new::<day20::Day20>(20),
+    Day::new::<day21::Day21>(21),
+    Day::new::<day22::Day22>(22),
+    Day::new::<day23::Day23>(23),
+];
+
+pub fn find(year: u16, day: u8) -> Option<&'static Day> {
+    DAYS.binary_search_by_key(&(year, day), |d| (d.year, d.day))
+        .ok()
+        .map(|i| &DAYS[i])
+}
+
+// Parses a day selection such as `7`, `1,5,9`, `10-18` or `1-3,12`.
+pub fn parse_days(spec: &str) -> Result<Vec<u8>, String> {
+    let parse_day = |s: &str| match s.trim().parse::<u8>() {
+        Ok(d @ 1..=25) => Ok(d),
+        _ => Err(format!("invalid day '{}', expected 1..=25", s.trim())),
+    };
+    let mut days = spec
+        .split(',')
+        .map(|part| match part.split_once('-') {
+            Some((a, b)) => {
+                let (a, b) = (parse_day(a)?, parse_day(b)?);
+                if a > b {
+                    return Err(format!("invalid day range '{}'", part.trim()));
+                }
+                Ok((a..=b).collect::<Vec<u8>>())
+            }
+            None => Ok(vec![parse_day(part)?]),
+        })
+        .collect::<Result<Vec<Vec<u8>>, String>>()?
+        .concat();
+    days.sort();
+    days.dedup();
+    Ok(days)
+}
+
+#[test]
+fn test_parse_days() {
+    assert_eq!(parse_days("1"), Ok(vec![1]));
+    assert_eq!(parse_days("9,1,5"), Ok(vec![1, 5, 9]));
+    assert_eq!(parse_days("10-13,12"), Ok(vec![10, 11, 12, 13]));
+    assert!(parse_days("0").is_err());
+    assert!(parse_days("26").is_err());
+    assert!(parse_days("5-3").is_err());
+    assert!(parse_days("x").is_err());
+}
+
+#[test]
+fn test_registry() {
+    assert!(DAYS
+        .windows(2)
+        .all(|w| (w[0].year, w[0].day) < (w[1].year, w[1].day)));
+    assert_eq!(find(YEAR, 12).unwrap().day, 12);
+    assert!(find(YEAR, 25).is_none());
+}
@@ -1,16 +1,42 @@
+#[cfg(test)]
 use std::fs;
 
-fn solution(input: &str) -> i32 {
+use crate::{error::AocError, solver::Solver};
+
+pub fn solution(input: &str) -> i32 {
+    input.lines().count() as i32
+}
+
+pub fn solution2(input: &str) -> i32 {
     input.lines().count() as i32
 }
 
 #[test]
+#[ignore = "template"]
 fn test_run() {
     let input = fs::read_to_string("src/inputs/aoc_XX_sample.input").unwrap();
-    assert_eq!(solution(&input), 0);
+    let input = DayXX::parse(&input).unwrap();
+    // Fill in the answers for the sample input from the puzzle description.
+    assert_eq!(solution(input), -1);
+    assert_eq!(solution2(input), -1);
 }
 
-pub fn run() -> (String, String) {
-    let input = fs::read_to_string("src/inputs/aoc_XX.input").unwrap();
-    (solution(&input).to_string(), solution(&input).to_string())
+pub struct DayXX;
+
+impl Solver for DayXX {
+    type Input<'a> = &'a str;
+    type Output1 = i32;
+    type Output2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, AocError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> i32 {
+        solution(input)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> i32 {
+        solution2(input)
+    }
 }